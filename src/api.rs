@@ -1,11 +1,67 @@
-use std::sync::Arc;
+use std::{
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
 use tokio::{sync::Semaphore, task::JoinSet};
 
-const API_SERVER: &str = "all.api.radio-browser.info";
+pub const DEFAULT_API_SERVER: &str = "all.api.radio-browser.info";
 
-#[derive(Debug, Clone, Deserialize)]
+const STREAM_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    Network(String),
+    Http(reqwest::StatusCode),
+    Parse(String),
+    NotFound(String),
+    Api(String),
+    InvalidQuery(String),
+    Timeout,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Network(err) => write!(f, "Request failed: {}", err),
+            Self::Http(status) => write!(f, "API error: {}", status),
+            Self::Parse(err) => write!(f, "Parse error: {}", err),
+            Self::NotFound(what) => write!(f, "Not found: {}", what),
+            Self::Api(message) => write!(f, "API: {}", message),
+            Self::InvalidQuery(message) => write!(f, "{}", message),
+            Self::Timeout => write!(f, "Request timed out"),
+        }
+    }
+}
+
+impl ApiError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Network(_) | Self::Timeout => true,
+            Self::Http(status) => status.is_server_error(),
+            Self::Parse(_) | Self::NotFound(_) | Self::Api(_) | Self::InvalidQuery(_) => false,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ApiError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            Self::Timeout
+        } else if err.is_decode() {
+            Self::Parse(err.to_string())
+        } else if let Some(status) = err.status() {
+            Self::Http(status)
+        } else {
+            Self::Network(err.to_string())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Station {
     pub stationuuid: String,
     pub name: String,
@@ -18,19 +74,139 @@ pub struct Station {
     pub country_code: String,
     #[serde(default)]
     pub language: String,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "number_or_string")]
     pub bitrate: u32,
+    #[serde(default)]
+    pub favicon: String,
+    #[serde(default)]
+    pub votes: u32,
+    #[serde(default)]
+    pub codec: String,
+    #[serde(default)]
+    pub homepage: String,
+}
+
+fn number_or_string<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(u32),
+        String(String),
+    }
+
+    match Option::<NumberOrString>::deserialize(deserializer)? {
+        None => Ok(0),
+        Some(NumberOrString::Number(value)) => Ok(value),
+        Some(NumberOrString::String(value)) if value.trim().is_empty() => Ok(0),
+        Some(NumberOrString::String(value)) => value
+            .trim()
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("invalid bitrate {:?}", value))),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ServerEntry {
+    name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiConfig {
+    pub max_retries: u32,
+    pub retry_delay: Duration,
+    pub request_timeout: Duration,
+    pub connect_timeout: Duration,
+    pub total_timeout: Duration,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            retry_delay: Duration::from_millis(200),
+            request_timeout: Duration::from_secs(10),
+            connect_timeout: Duration::from_secs(5),
+            total_timeout: Duration::from_secs(20),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerPool {
+    servers: Arc<Mutex<Vec<String>>>,
+    config: ApiConfig,
+}
+
+impl ServerPool {
+    pub fn new(servers: Vec<String>) -> Self {
+        Self {
+            servers: Arc::new(Mutex::new(servers)),
+            config: ApiConfig::default(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_config(mut self, config: ApiConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn current(&self) -> String {
+        self.candidates()
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| server_base_url(DEFAULT_API_SERVER))
+    }
+
+    pub fn candidates(&self) -> Vec<String> {
+        self.servers
+            .lock()
+            .map(|servers| servers.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn replace(&self, servers: Vec<String>) {
+        if servers.is_empty() {
+            return;
+        }
+        if let Ok(mut current) = self.servers.lock() {
+            *current = servers;
+        }
+    }
+
+    fn promote(&self, server: &str) {
+        if let Ok(mut servers) = self.servers.lock()
+            && let Some(index) = servers.iter().position(|candidate| candidate == server)
+        {
+            let promoted = servers.remove(index);
+            servers.insert(0, promoted);
+        }
+    }
+}
+
+impl Default for ServerPool {
+    fn default() -> Self {
+        Self::new(vec![server_base_url(DEFAULT_API_SERVER)])
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct SearchParams {
     pub name: String,
     pub tags: String,
+    pub exact_tags: bool,
     pub country: String,
+    pub state: String,
     pub language: String,
-    pub bitrate: Option<u32>,
+    pub bitrate_min: Option<u32>,
+    pub bitrate_max: Option<u32>,
+    pub codec: String,
     pub limit: u32,
     pub offset: u32,
+    pub reverse: bool,
 }
 
 impl Default for SearchParams {
@@ -38,22 +214,44 @@ impl Default for SearchParams {
         Self {
             name: String::new(),
             tags: String::new(),
+            exact_tags: true,
             country: String::new(),
+            state: String::new(),
             language: String::new(),
-            bitrate: None,
+            bitrate_min: None,
+            bitrate_max: None,
+            codec: String::new(),
             limit: 50,
             offset: 0,
+            reverse: true,
         }
     }
 }
 
-fn search_query(params: &SearchParams) -> Vec<(&'static str, String)> {
+impl SearchParams {
+    pub fn is_default_query(&self) -> bool {
+        let defaults = Self::default();
+        self.name.is_empty()
+            && self.tags.is_empty()
+            && self.country.is_empty()
+            && self.state.is_empty()
+            && self.language.is_empty()
+            && self.bitrate_min.is_none()
+            && self.bitrate_max.is_none()
+            && self.codec.is_empty()
+            && self.offset == 0
+            && self.limit == defaults.limit
+            && self.reverse == defaults.reverse
+    }
+}
+
+fn search_query(params: &SearchParams) -> Result<Vec<(&'static str, String)>, ApiError> {
     let mut query = vec![
         ("limit", params.limit.to_string()),
         ("offset", params.offset.to_string()),
         ("hidebroken", "true".to_string()),
         ("order", "clickcount".to_string()),
-        ("reverse", "true".to_string()),
+        ("reverse", params.reverse.to_string()),
     ];
 
     let name = params.name.trim();
@@ -63,7 +261,14 @@ fn search_query(params: &SearchParams) -> Vec<(&'static str, String)> {
 
     let tags = params.tags.trim();
     if !tags.is_empty() {
-        query.push(("tagList", tags.to_string()));
+        if !params.exact_tags && tags.contains(',') {
+            return Err(ApiError::InvalidQuery(
+                "Partial tag matching takes a single tag; set exact_tags to search several"
+                    .to_string(),
+            ));
+        }
+        let key = if params.exact_tags { "tagList" } else { "tag" };
+        query.push((key, tags.to_string()));
     }
 
     let country = params.country.trim();
@@ -71,105 +276,447 @@ fn search_query(params: &SearchParams) -> Vec<(&'static str, String)> {
         query.push(("countrycode", country.to_uppercase()));
     }
 
+    let state = params.state.trim();
+    if !state.is_empty() {
+        query.push(("state", state.to_string()));
+    }
+
     let language = params.language.trim();
     if !language.is_empty() {
         query.push(("language", language.to_lowercase()));
     }
 
-    if let Some(bitrate) = params.bitrate {
+    if let Some(bitrate) = params.bitrate_min {
         query.push(("bitrateMin", bitrate.to_string()));
     }
 
-    query
+    if let Some(bitrate) = params.bitrate_max {
+        query.push(("bitrateMax", bitrate.to_string()));
+    }
+
+    let codec = params.codec.trim();
+    if !codec.is_empty() {
+        query.push(("codec", codec.to_string()));
+    }
+
+    Ok(query)
 }
 
-fn filter_stations_by_bitrate(mut stations: Vec<Station>, bitrate: Option<u32>) -> Vec<Station> {
-    if let Some(bitrate) = bitrate {
-        stations.retain(|station| station.bitrate >= bitrate);
+fn filter_stations_by_bitrate(
+    mut stations: Vec<Station>,
+    min: Option<u32>,
+    max: Option<u32>,
+) -> Vec<Station> {
+    if let Some(min) = min {
+        stations.retain(|station| station.bitrate >= min);
+    }
+    if let Some(max) = max {
+        stations.retain(|station| station.bitrate <= max);
     }
 
     stations
 }
 
-pub async fn search_stations(
-    client: &reqwest::Client,
-    params: &SearchParams,
-) -> Result<Vec<Station>, String> {
-    let url = format!("https://{}/json/stations/search", API_SERVER);
-    let query = search_query(params);
+fn server_base_url(name: &str) -> String {
+    format!("https://{}", name)
+}
 
-    let response = client
+fn order_servers(servers: Vec<ServerEntry>, seed: usize) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for server in servers {
+        let name = server.name.trim().to_lowercase();
+        if !name.is_empty() && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names.sort();
+    if !names.is_empty() {
+        let len = names.len();
+        names.rotate_left(seed % len);
+    }
+
+    let mut ordered: Vec<String> = names.iter().map(|name| server_base_url(name)).collect();
+    ordered.push(server_base_url(DEFAULT_API_SERVER));
+    ordered
+}
+
+async fn fetch_server_list(client: &reqwest::Client, base_url: &str) -> Vec<ServerEntry> {
+    let url = format!("{}/json/servers", base_url);
+    match client
         .get(&url)
         .header("User-Agent", "cradio/0.1")
-        .query(&query)
         .send()
         .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+    {
+        Ok(response) if response.status().is_success() => response.json().await.unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
 
+pub async fn resolve_api_servers(client: &reqwest::Client) -> Vec<String> {
+    let servers = fetch_server_list(client, &server_base_url(DEFAULT_API_SERVER)).await;
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos() as usize)
+        .unwrap_or_default();
+    order_servers(servers, seed)
+}
+
+async fn send_with_fallback(
+    servers: &ServerPool,
+    request: impl Fn(&str) -> reqwest::RequestBuilder,
+) -> Result<reqwest::Response, ApiError> {
+    let attempts = async {
+        let mut attempt = 0;
+        loop {
+            let err = match send_to_mirrors(servers, &request).await {
+                Ok(response) => return Ok(response),
+                Err(err) => err,
+            };
+            if !err.is_retryable() || attempt >= servers.config.max_retries {
+                return Err(err);
+            }
+            tokio::time::sleep(servers.config.retry_delay * 2u32.saturating_pow(attempt)).await;
+            attempt += 1;
+        }
+    };
+    tokio::time::timeout(servers.config.total_timeout, attempts)
+        .await
+        .unwrap_or(Err(ApiError::Timeout))
+}
+
+async fn send_to_mirrors(
+    servers: &ServerPool,
+    request: &impl Fn(&str) -> reqwest::RequestBuilder,
+) -> Result<reqwest::Response, ApiError> {
+    let mut last_err = ApiError::Network("no API servers available".to_string());
+
+    for server in servers.candidates() {
+        match request(&server)
+            .header("User-Agent", "cradio/0.1")
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_server_error() => {
+                last_err = ApiError::Http(response.status());
+            }
+            Ok(response) => {
+                servers.promote(&server);
+                return Ok(response);
+            }
+            Err(e) => last_err = e.into(),
+        }
+    }
+
+    Err(last_err)
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    message: String,
+}
+
+async fn parse_stations(response: reqwest::Response) -> Result<Vec<Station>, ApiError> {
     if !response.status().is_success() {
-        return Err(format!("API error: {}", response.status()));
+        return Err(ApiError::Http(response.status()));
     }
+    let body = response.bytes().await?;
+    serde_json::from_slice(&body).map_err(|err| match serde_json::from_slice::<ErrorBody>(&body) {
+        Ok(error) => ApiError::Api(error.message),
+        Err(_) => ApiError::Parse(err.to_string()),
+    })
+}
 
-    let stations: Vec<Station> = response
-        .json()
-        .await
-        .map_err(|e| format!("Parse error: {}", e))?;
+pub fn build_client(config: &ApiConfig) -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(config.request_timeout)
+        .connect_timeout(config.connect_timeout)
+        .gzip(true)
+        .brotli(true)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+pub async fn search_stations(
+    client: &reqwest::Client,
+    servers: &ServerPool,
+    params: &SearchParams,
+) -> Result<(Vec<Station>, usize), ApiError> {
+    let query = search_query(params)?;
 
-    Ok(filter_stations_by_bitrate(stations, params.bitrate))
+    let response = send_with_fallback(servers, |server| {
+        client
+            .get(format!("{}/json/stations/search", server))
+            .query(&query)
+    })
+    .await?;
+    let stations = parse_stations(response).await?;
+
+    let fetched = stations.len();
+    Ok((
+        filter_stations_by_bitrate(stations, params.bitrate_min, params.bitrate_max),
+        fetched,
+    ))
 }
 
-async fn fetch_station_by_uuid(
+pub trait StationSource {
+    fn search(
+        &self,
+        client: &reqwest::Client,
+        params: &SearchParams,
+    ) -> impl Future<Output = Result<(Vec<Station>, usize), ApiError>> + Send;
+}
+
+#[derive(Debug, Clone)]
+pub struct RadioBrowser {
+    pub servers: ServerPool,
+}
+
+impl StationSource for RadioBrowser {
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        params: &SearchParams,
+    ) -> Result<(Vec<Station>, usize), ApiError> {
+        search_stations(client, &self.servers, params).await
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomDirectory {
+    pub endpoint: String,
+}
+
+impl StationSource for CustomDirectory {
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        params: &SearchParams,
+    ) -> Result<(Vec<Station>, usize), ApiError> {
+        let response = client
+            .get(&self.endpoint)
+            .query(&search_query(params)?)
+            .header("User-Agent", "cradio/0.1")
+            .send()
+            .await?;
+        let stations = parse_stations(response).await?;
+
+        let fetched = stations.len();
+        Ok((
+            filter_stations_by_bitrate(stations, params.bitrate_min, params.bitrate_max),
+            fetched,
+        ))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Directory {
+    RadioBrowser(RadioBrowser),
+    Custom(CustomDirectory),
+}
+
+impl Directory {
+    pub fn from_settings(custom_endpoint: Option<&str>, servers: &ServerPool) -> Self {
+        match custom_endpoint.map(str::trim) {
+            Some(endpoint) if !endpoint.is_empty() => Self::Custom(CustomDirectory {
+                endpoint: endpoint.to_string(),
+            }),
+            _ => Self::RadioBrowser(RadioBrowser {
+                servers: servers.clone(),
+            }),
+        }
+    }
+}
+
+impl StationSource for Directory {
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        params: &SearchParams,
+    ) -> Result<(Vec<Station>, usize), ApiError> {
+        match self {
+            Self::RadioBrowser(source) => source.search(client, params).await,
+            Self::Custom(source) => source.search(client, params).await,
+        }
+    }
+}
+
+fn alternate_scheme_url(url: &str) -> Option<String> {
+    if let Some(rest) = url.strip_prefix("https://") {
+        Some(format!("http://{}", rest))
+    } else {
+        url.strip_prefix("http://")
+            .map(|rest| format!("https://{}", rest))
+    }
+}
+
+pub fn adhoc_station(input: &str) -> Result<Station, String> {
+    let url =
+        reqwest::Url::parse(input.trim()).map_err(|e| format!("Invalid stream URL: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("Stream URL must start with http:// or https://".to_string());
+    }
+    let host = url
+        .host_str()
+        .filter(|host| !host.is_empty())
+        .ok_or_else(|| "Stream URL has no host".to_string())?;
+    let name = match url.path().trim_matches('/') {
+        "" => host.to_string(),
+        path => format!("{}/{}", host, path),
+    };
+
+    Ok(Station {
+        stationuuid: format!("url:{}", url),
+        name,
+        url: url.to_string(),
+        url_resolved: url.to_string(),
+        tags: String::new(),
+        country_code: String::new(),
+        language: String::new(),
+        bitrate: 0,
+        favicon: String::new(),
+        votes: 0,
+        codec: String::new(),
+        homepage: String::new(),
+    })
+}
+
+pub fn stream_candidates(station: &Station, prefer_resolved: bool) -> Vec<String> {
+    let mut primary = [station.url_resolved.trim(), station.url.trim()];
+    if !prefer_resolved {
+        primary.reverse();
+    }
+    let primary: Vec<&str> = primary.into_iter().filter(|url| !url.is_empty()).collect();
+
+    let mut candidates: Vec<String> = Vec::new();
+    let alternates = primary.iter().filter_map(|url| alternate_scheme_url(url));
+    for url in primary.iter().map(|url| url.to_string()).chain(alternates) {
+        if !candidates.contains(&url) {
+            candidates.push(url);
+        }
+    }
+    candidates
+}
+
+pub async fn check_stream(client: &reqwest::Client, urls: &[String]) -> Result<String, String> {
+    let mut last_err = "Station has no stream URL".to_string();
+
+    for url in urls {
+        match client
+            .get(url)
+            .header("User-Agent", "cradio/0.1")
+            .header("Range", "bytes=0-0")
+            .timeout(STREAM_CHECK_TIMEOUT)
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_success() => return Ok(url.clone()),
+            Ok(response) => last_err = format!("Stream error: {}", response.status()),
+            Err(e) => last_err = format!("Stream unreachable: {}", e),
+        }
+    }
+
+    Err(last_err)
+}
+
+fn click_url(server: &str, station_uuid: &str) -> String {
+    format!("{}/json/url/{}", server, station_uuid)
+}
+
+pub async fn register_click(client: &reqwest::Client, server: &str, station_uuid: &str) {
+    let _ = client
+        .post(click_url(server, station_uuid))
+        .header("User-Agent", "cradio/0.1")
+        .send()
+        .await;
+}
+
+#[derive(Debug, Deserialize)]
+struct VoteResponse {
+    ok: bool,
+    #[serde(default)]
+    message: String,
+}
+
+const ALREADY_VOTED: &str = "Already voted today";
+
+fn vote_url(server: &str, station_uuid: &str) -> String {
+    format!("{}/json/vote/{}", server, station_uuid)
+}
+
+pub async fn vote_station(
     client: &reqwest::Client,
     server: &str,
     station_uuid: &str,
-) -> Result<Option<Station>, String> {
-    let url = format!("https://{}/json/stations/byuuid/{}", server, station_uuid);
+) -> Result<(), String> {
     let response = client
-        .get(&url)
+        .post(vote_url(server, station_uuid))
         .header("User-Agent", "cradio/0.1")
         .send()
         .await
-        .map_err(|e| format!("Request failed for {}: {}", station_uuid, e))?;
-
-    if !response.status().is_success() {
-        return Err(format!(
-            "API error for {}: {}",
-            station_uuid,
-            response.status()
-        ));
+        .map_err(|e| format!("Vote failed: {}", e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(format!("Vote failed: station {} not found", station_uuid));
     }
-
-    let stations: Vec<Station> = response
+    if response.status().is_client_error() {
+        return Err(ALREADY_VOTED.to_string());
+    }
+    let response = response
+        .error_for_status()
+        .map_err(|e| format!("Vote failed: {}", e))?;
+    let vote: VoteResponse = response
         .json()
         .await
-        .map_err(|e| format!("Parse error for {}: {}", station_uuid, e))?;
+        .map_err(|e| format!("Vote failed: {}", e))?;
+    if vote.ok {
+        Ok(())
+    } else if vote.message.contains("too often") {
+        Err(ALREADY_VOTED.to_string())
+    } else {
+        Err(format!("Vote rejected: {}", vote.message))
+    }
+}
+
+async fn fetch_station_by_uuid(
+    client: &reqwest::Client,
+    servers: &ServerPool,
+    station_uuid: &str,
+) -> Result<Station, ApiError> {
+    let response = send_with_fallback(servers, |server| {
+        client.get(format!("{}/json/stations/byuuid/{}", server, station_uuid))
+    })
+    .await?;
 
-    Ok(stations.into_iter().next())
+    parse_stations(response)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| ApiError::NotFound(station_uuid.to_string()))
 }
 
 pub async fn fetch_stations_by_uuids(
     client: &reqwest::Client,
+    servers: &ServerPool,
     station_uuids: Vec<String>,
-) -> (Vec<Station>, Vec<String>) {
+) -> (Vec<(String, Station)>, Vec<String>) {
     if station_uuids.is_empty() {
         return (Vec::new(), Vec::new());
     }
 
-    let server = API_SERVER.to_string();
     let semaphore = Arc::new(Semaphore::new(8));
     let mut join_set = JoinSet::new();
 
     for station_uuid in station_uuids {
         let client = client.clone();
-        let server = server.clone();
+        let servers = servers.clone();
         let semaphore = Arc::clone(&semaphore);
         join_set.spawn(async move {
             let _permit = semaphore
                 .acquire_owned()
                 .await
                 .map_err(|e| format!("Concurrency control error: {}", e))?;
-            let result = fetch_station_by_uuid(&client, &server, &station_uuid).await;
-            Ok::<(String, Result<Option<Station>, String>), String>((station_uuid, result))
+            let result = fetch_station_by_uuid(&client, &servers, &station_uuid).await;
+            Ok::<(String, Result<Station, ApiError>), String>((station_uuid, result))
         });
     }
 
@@ -178,11 +725,7 @@ pub async fn fetch_stations_by_uuids(
 
     while let Some(result) = join_set.join_next().await {
         match result {
-            Ok(Ok((station_uuid, Ok(Some(station))))) => {
-                stations.push(station);
-                let _ = station_uuid;
-            }
-            Ok(Ok((station_uuid, Ok(None)))) => failed_uuids.push(station_uuid),
+            Ok(Ok((station_uuid, Ok(station)))) => stations.push((station_uuid, station)),
             Ok(Ok((station_uuid, Err(_)))) => failed_uuids.push(station_uuid),
             Ok(Err(_)) => {}
             Err(_) => {}
@@ -194,7 +737,53 @@ pub async fn fetch_stations_by_uuids(
 
 #[cfg(test)]
 mod tests {
-    use super::{SearchParams, Station, filter_stations_by_bitrate, search_query};
+    use super::{
+        ApiConfig, ApiError, Directory, SearchParams, ServerEntry, ServerPool, Station,
+        StationSource, adhoc_station, build_client, check_stream, click_url, fetch_server_list,
+        fetch_station_by_uuid, filter_stations_by_bitrate, order_servers, register_click,
+        search_query, search_stations, stream_candidates, vote_station, vote_url,
+    };
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::oneshot,
+    };
+
+    const NO_RETRIES: ApiConfig = ApiConfig {
+        max_retries: 0,
+        retry_delay: Duration::ZERO,
+        request_timeout: Duration::from_secs(10),
+        connect_timeout: Duration::from_secs(5),
+        total_timeout: Duration::from_secs(20),
+    };
+
+    async fn scripted_server(statuses: &'static [&'static str]) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let hit = counter.fetch_add(1, Ordering::SeqCst);
+                let status = statuses[hit.min(statuses.len() - 1)];
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let head = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: 2\r\nConnection: close\r\n\r\n[]",
+                    status
+                );
+                let _ = socket.write_all(head.as_bytes()).await;
+            }
+        });
+        (format!("http://{}", addr), hits)
+    }
 
     fn station(id: &str, bitrate: u32) -> Station {
         Station {
@@ -206,13 +795,50 @@ mod tests {
             country_code: String::new(),
             language: String::new(),
             bitrate,
+            favicon: String::new(),
+            votes: 0,
+            codec: String::new(),
+            homepage: String::new(),
         }
     }
 
+    #[test]
+    fn only_unfiltered_first_pages_count_as_the_default_query() {
+        assert!(SearchParams::default().is_default_query());
+        assert!(
+            !SearchParams {
+                reverse: false,
+                ..SearchParams::default()
+            }
+            .is_default_query()
+        );
+        assert!(
+            !SearchParams {
+                limit: 100,
+                ..SearchParams::default()
+            }
+            .is_default_query()
+        );
+        assert!(
+            !SearchParams {
+                codec: "MP3".to_string(),
+                ..SearchParams::default()
+            }
+            .is_default_query()
+        );
+        assert!(
+            !SearchParams {
+                offset: 50,
+                ..SearchParams::default()
+            }
+            .is_default_query()
+        );
+    }
+
     #[test]
     fn search_query_contains_defaults_for_empty_filters() {
         let params = SearchParams::default();
-        let query = search_query(&params);
+        let query = search_query(&params).expect("valid query");
 
         assert_eq!(
             query,
@@ -231,14 +857,19 @@ mod tests {
         let params = SearchParams {
             name: " Jazz FM ".to_string(),
             tags: " jazz,blues ".to_string(),
+            exact_tags: true,
             country: "de".to_string(),
+            state: " Bavaria ".to_string(),
             language: "EN".to_string(),
-            bitrate: Some(128),
+            bitrate_min: Some(128),
+            bitrate_max: Some(320),
+            codec: "AAC".to_string(),
             limit: 25,
             offset: 50,
+            reverse: false,
         };
 
-        let query = search_query(&params);
+        let query = search_query(&params).expect("valid query");
 
         assert_eq!(
             query,
@@ -247,16 +878,47 @@ mod tests {
                 ("offset", "50".to_string()),
                 ("hidebroken", "true".to_string()),
                 ("order", "clickcount".to_string()),
-                ("reverse", "true".to_string()),
+                ("reverse", "false".to_string()),
                 ("name", "Jazz FM".to_string()),
                 ("tagList", "jazz,blues".to_string()),
                 ("countrycode", "DE".to_string()),
+                ("state", "Bavaria".to_string()),
                 ("language", "en".to_string()),
                 ("bitrateMin", "128".to_string()),
+                ("bitrateMax", "320".to_string()),
+                ("codec", "AAC".to_string()),
             ]
         );
     }
 
+    #[test]
+    fn partial_tag_matching_uses_the_tag_parameter() {
+        let params = SearchParams {
+            tags: "electro".to_string(),
+            exact_tags: false,
+            ..SearchParams::default()
+        };
+
+        let query = search_query(&params).expect("single tag");
+
+        assert!(query.contains(&("tag", "electro".to_string())));
+        assert!(!query.iter().any(|(key, _)| *key == "tagList"));
+    }
+
+    #[test]
+    fn partial_tag_matching_rejects_tag_lists() {
+        let params = SearchParams {
+            tags: "electro,house".to_string(),
+            exact_tags: false,
+            ..SearchParams::default()
+        };
+
+        assert!(matches!(
+            search_query(&params),
+            Err(ApiError::InvalidQuery(_))
+        ));
+    }
+
     #[test]
     fn bitrate_filter_keeps_only_matching_stations() {
         let stations = vec![
@@ -264,21 +926,650 @@ mod tests {
             station("mid", 128),
             station("high", 192),
         ];
-        let filtered = filter_stations_by_bitrate(stations, Some(128));
+        let filtered = filter_stations_by_bitrate(stations.clone(), Some(128), None);
 
         assert_eq!(filtered.len(), 2);
         assert!(filtered.iter().all(|station| station.bitrate >= 128));
         assert_eq!(filtered[0].stationuuid, "mid");
         assert_eq!(filtered[1].stationuuid, "high");
+
+        let capped = filter_stations_by_bitrate(stations.clone(), None, Some(128));
+        let ids: Vec<&str> = capped.iter().map(|s| s.stationuuid.as_str()).collect();
+        assert_eq!(ids, vec!["low", "mid"]);
+
+        let band = filter_stations_by_bitrate(stations, Some(100), Some(150));
+        assert_eq!(band.len(), 1);
+        assert_eq!(band[0].stationuuid, "mid");
     }
 
     #[test]
     fn bitrate_filter_leaves_stations_unchanged_without_threshold() {
         let stations = vec![station("a", 32), station("b", 256)];
-        let filtered = filter_stations_by_bitrate(stations.clone(), None);
+        let filtered = filter_stations_by_bitrate(stations.clone(), None, None);
 
         assert_eq!(filtered.len(), 2);
         assert_eq!(filtered[0].stationuuid, stations[0].stationuuid);
         assert_eq!(filtered[1].stationuuid, stations[1].stationuuid);
     }
+
+    fn server(name: &str) -> ServerEntry {
+        ServerEntry {
+            name: name.to_string(),
+        }
+    }
+
+    async fn mock_server(status: &'static str, body: &'static str) -> String {
+        mock_server_with_headers(status, "", body.as_bytes()).await
+    }
+
+    async fn mock_server_with_headers(
+        status: &'static str,
+        headers: &'static str,
+        body: &'static [u8],
+    ) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let head = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    headers,
+                    body.len()
+                );
+                let _ = socket.write_all(head.as_bytes()).await;
+                let _ = socket.write_all(body).await;
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    async fn recording_server(body: &'static str) -> (String, oneshot::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            if let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let read = socket.read(&mut buf).await.unwrap_or_default();
+                let _ = tx.send(String::from_utf8_lossy(&buf[..read]).to_lowercase());
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(head.as_bytes()).await;
+            }
+        });
+        (format!("http://{}", addr), rx)
+    }
+
+    async fn dead_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        drop(listener);
+        format!("http://{}", addr)
+    }
+
+    #[test]
+    fn bitrate_accepts_numbers_numeric_strings_and_null() {
+        let parse = |bitrate: &str| {
+            serde_json::from_str::<Station>(&format!(
+                r#"{{"stationuuid":"a","name":"A","url":"https://a","bitrate":{}}}"#,
+                bitrate
+            ))
+            .map(|station| station.bitrate)
+        };
+
+        assert_eq!(parse("128").expect("number"), 128);
+        assert_eq!(parse(r#""192""#).expect("numeric string"), 192);
+        assert_eq!(parse(r#"" 64 ""#).expect("padded string"), 64);
+        assert_eq!(parse(r#" "" "#).expect("empty string"), 0);
+        assert_eq!(parse("null").expect("null"), 0);
+        assert!(parse(r#""fast""#).is_err());
+
+        let missing: Station =
+            serde_json::from_str(r#"{"stationuuid":"a","name":"A","url":"https://a"}"#)
+                .expect("missing bitrate");
+        assert_eq!(missing.bitrate, 0);
+    }
+
+    #[test]
+    fn adhoc_station_uses_the_url_for_playback_and_naming() {
+        let station = adhoc_station(" https://stream.example.com/live/aac ").expect("valid url");
+        assert_eq!(station.url, "https://stream.example.com/live/aac");
+        assert_eq!(station.url_resolved, station.url);
+        assert_eq!(station.name, "stream.example.com/live/aac");
+        assert_eq!(
+            station.stationuuid,
+            "url:https://stream.example.com/live/aac"
+        );
+
+        let bare = adhoc_station("http://radio.example:8000").expect("valid url");
+        assert_eq!(bare.name, "radio.example");
+    }
+
+    #[test]
+    fn click_url_points_at_the_station_url_endpoint() {
+        assert_eq!(
+            click_url("https://de1.api.radio-browser.info", "uuid-1"),
+            "https://de1.api.radio-browser.info/json/url/uuid-1"
+        );
+    }
+
+    #[tokio::test]
+    async fn register_click_identifies_the_client() {
+        let (server, request) = recording_server(r#"{"ok":true}"#).await;
+        register_click(&reqwest::Client::new(), &server, "uuid-1").await;
+
+        let request = request.await.expect("request");
+        assert!(request.starts_with("post /json/url/uuid-1 "));
+        assert!(request.contains("user-agent: cradio/0.1"));
+    }
+
+    #[test]
+    fn vote_url_points_at_the_vote_endpoint() {
+        assert_eq!(
+            vote_url("https://de1.api.radio-browser.info", "uuid-1"),
+            "https://de1.api.radio-browser.info/json/vote/uuid-1"
+        );
+    }
+
+    #[tokio::test]
+    async fn vote_station_reports_success_and_rate_limits() {
+        let client = reqwest::Client::new();
+        let ok = mock_server(
+            "200 OK",
+            r#"{"ok":true,"message":"voted for station successfully"}"#,
+        )
+        .await;
+        assert_eq!(vote_station(&client, &ok, "uuid-1").await, Ok(()));
+
+        let limited = mock_server(
+            "200 OK",
+            r#"{"ok":false,"message":"VoteError you are voting for the same station too often"}"#,
+        )
+        .await;
+        assert_eq!(
+            vote_station(&client, &limited, "uuid-1").await,
+            Err("Already voted today".to_string())
+        );
+
+        let rejected = mock_server("429 Too Many Requests", "{}").await;
+        assert_eq!(
+            vote_station(&client, &rejected, "uuid-1").await,
+            Err("Already voted today".to_string())
+        );
+
+        let broken = mock_server("500 Internal Server Error", "{}").await;
+        let err = vote_station(&client, &broken, "uuid-1")
+            .await
+            .expect_err("server error");
+        assert!(err.starts_with("Vote failed: "));
+    }
+
+    #[tokio::test]
+    async fn vote_station_identifies_the_client() {
+        let (server, request) = recording_server(r#"{"ok":true,"message":"voted"}"#).await;
+        assert_eq!(
+            vote_station(&reqwest::Client::new(), &server, "uuid-1").await,
+            Ok(())
+        );
+
+        let request = request.await.expect("request");
+        assert!(request.starts_with("post /json/vote/uuid-1 "));
+        assert!(request.contains("user-agent: cradio/0.1"));
+    }
+
+    #[tokio::test]
+    async fn vote_station_treats_client_errors_as_already_voted_only() {
+        let client = reqwest::Client::new();
+        for status in [
+            "400 Bad Request",
+            "403 Forbidden",
+            "409 Conflict",
+            "429 Too Many Requests",
+        ] {
+            let server = mock_server(status, "{}").await;
+            assert_eq!(
+                vote_station(&client, &server, "uuid-1").await,
+                Err("Already voted today".to_string()),
+                "{}",
+                status
+            );
+        }
+
+        let server = mock_server("404 Not Found", "{}").await;
+        assert_eq!(
+            vote_station(&client, &server, "uuid-1").await,
+            Err("Vote failed: station uuid-1 not found".to_string())
+        );
+
+        for status in [
+            "500 Internal Server Error",
+            "502 Bad Gateway",
+            "503 Service Unavailable",
+        ] {
+            let server = mock_server(status, "{}").await;
+            let err = vote_station(&client, &server, "uuid-1")
+                .await
+                .expect_err("server error");
+            assert!(err.starts_with("Vote failed: "), "{}", err);
+            assert!(err.contains(&status[..3]), "{}", err);
+        }
+
+        let err = vote_station(&client, &dead_server().await, "uuid-1")
+            .await
+            .expect_err("transport error");
+        assert!(err.starts_with("Vote failed: "), "{}", err);
+    }
+
+    #[test]
+    fn adhoc_station_rejects_invalid_input() {
+        assert!(adhoc_station("not a url").is_err());
+        assert_eq!(
+            adhoc_station("ftp://radio.example/stream").map(|s| s.name),
+            Err("Stream URL must start with http:// or https://".to_string())
+        );
+    }
+
+    #[test]
+    fn order_servers_dedups_mirrors_rotates_by_seed_and_keeps_default_last() {
+        let servers = || {
+            vec![
+                server("de1.api.radio-browser.info"),
+                server("DE1.api.radio-browser.info"),
+                server("at1.api.radio-browser.info"),
+                server(" "),
+            ]
+        };
+
+        assert_eq!(
+            order_servers(servers(), 0),
+            vec![
+                "https://at1.api.radio-browser.info".to_string(),
+                "https://de1.api.radio-browser.info".to_string(),
+                "https://all.api.radio-browser.info".to_string(),
+            ]
+        );
+        assert_eq!(
+            order_servers(servers(), 3)[0],
+            "https://de1.api.radio-browser.info"
+        );
+    }
+
+    #[test]
+    fn order_servers_falls_back_to_default_without_candidates() {
+        assert_eq!(
+            order_servers(Vec::new(), 7),
+            vec!["https://all.api.radio-browser.info".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn server_list_is_read_from_the_servers_endpoint() {
+        let client = reqwest::Client::new();
+        let base = mock_server(
+            "200 OK",
+            r#"[{"ip":"1.2.3.4","name":"de1.api.radio-browser.info"},{"name":"nl1.api.radio-browser.info"}]"#,
+        )
+        .await;
+        let names: Vec<String> = fetch_server_list(&client, &base)
+            .await
+            .into_iter()
+            .map(|server| server.name)
+            .collect();
+        assert_eq!(
+            names,
+            vec!["de1.api.radio-browser.info", "nl1.api.radio-browser.info"]
+        );
+
+        let failing = mock_server("500 Internal Server Error", "oops").await;
+        assert!(fetch_server_list(&client, &failing).await.is_empty());
+        assert!(
+            fetch_server_list(&client, &dead_server().await)
+                .await
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn server_pool_ignores_empty_replacement() {
+        let pool = ServerPool::default();
+        pool.replace(Vec::new());
+        assert_eq!(pool.current(), "https://all.api.radio-browser.info");
+    }
+
+    #[tokio::test]
+    async fn search_falls_back_to_next_mirror_on_connection_error() {
+        let dead = dead_server().await;
+        let healthy = mock_server("200 OK", "[]").await;
+        let pool = ServerPool::new(vec![dead, healthy.clone()]);
+
+        let (stations, _) =
+            search_stations(&reqwest::Client::new(), &pool, &SearchParams::default())
+                .await
+                .expect("fallback mirror should answer");
+
+        assert!(stations.is_empty());
+        assert_eq!(pool.current(), healthy);
+    }
+
+    #[tokio::test]
+    async fn search_decodes_gzip_encoded_responses() {
+        static GZIPPED_STATIONS: [u8; 73] = [
+            31, 139, 8, 0, 0, 0, 0, 0, 2, 3, 139, 174, 86, 42, 46, 73, 44, 201, 204, 207, 43, 45,
+            205, 76, 81, 178, 82, 74, 175, 82, 210, 81, 202, 75, 204, 77, 5, 178, 221, 171, 50, 11,
+            20, 220, 124, 129, 2, 165, 69, 57, 64, 126, 70, 73, 73, 65, 177, 149, 190, 62, 80, 77,
+            109, 44, 0, 171, 49, 188, 33, 58, 0, 0, 0,
+        ];
+        let server =
+            mock_server_with_headers("200 OK", "Content-Encoding: gzip\r\n", &GZIPPED_STATIONS)
+                .await;
+        let pool = ServerPool::new(vec![server]);
+        let client = build_client(&ApiConfig::default()).expect("client");
+
+        let (stations, _) = search_stations(&client, &pool, &SearchParams::default())
+            .await
+            .expect("gzip body should be decoded");
+
+        assert_eq!(stations.len(), 1);
+        assert_eq!(stations[0].name, "Gzip FM");
+    }
+
+    #[tokio::test]
+    async fn search_falls_back_to_next_mirror_on_server_error() {
+        let failing = mock_server("503 Service Unavailable", "").await;
+        let healthy = mock_server("200 OK", "[]").await;
+        let pool = ServerPool::new(vec![failing, healthy.clone()]);
+
+        search_stations(&reqwest::Client::new(), &pool, &SearchParams::default())
+            .await
+            .expect("fallback mirror should answer");
+
+        assert_eq!(pool.current(), healthy);
+    }
+
+    #[tokio::test]
+    async fn search_reports_last_error_when_all_mirrors_fail() {
+        let failing = mock_server("502 Bad Gateway", "").await;
+        let pool = ServerPool::new(vec![dead_server().await, failing]).with_config(NO_RETRIES);
+
+        let err = search_stations(&reqwest::Client::new(), &pool, &SearchParams::default())
+            .await
+            .expect_err("all mirrors fail");
+
+        assert_eq!(err, ApiError::Http(reqwest::StatusCode::BAD_GATEWAY));
+        assert_eq!(err.to_string(), "API error: 502 Bad Gateway");
+    }
+
+    #[tokio::test]
+    async fn transient_server_errors_are_retried_with_backoff() {
+        let (server, hits) =
+            scripted_server(&["503 Service Unavailable", "502 Bad Gateway", "200 OK"]).await;
+        let pool = ServerPool::new(vec![server]).with_config(ApiConfig {
+            max_retries: 3,
+            retry_delay: Duration::from_millis(1),
+            ..NO_RETRIES
+        });
+
+        search_stations(&reqwest::Client::new(), &pool, &SearchParams::default())
+            .await
+            .expect("third attempt should answer");
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        let (server, hits) =
+            scripted_server(&["500 Internal Server Error", "503 Service Unavailable"]).await;
+        let pool = ServerPool::new(vec![server]).with_config(ApiConfig {
+            max_retries: 2,
+            retry_delay: Duration::from_millis(1),
+            ..NO_RETRIES
+        });
+        let err = fetch_station_by_uuid(&reqwest::Client::new(), &pool, "uuid-1")
+            .await
+            .expect_err("every attempt fails");
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            err,
+            ApiError::Http(reqwest::StatusCode::SERVICE_UNAVAILABLE)
+        );
+    }
+
+    #[tokio::test]
+    async fn slow_servers_hit_the_request_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    drop(socket);
+                });
+            }
+        });
+        let config = ApiConfig {
+            request_timeout: Duration::from_millis(200),
+            ..NO_RETRIES
+        };
+        let client = build_client(&config).expect("client");
+        let pool = ServerPool::new(vec![format!("http://{}", addr)]).with_config(config);
+
+        let err = search_stations(&client, &pool, &SearchParams::default())
+            .await
+            .expect_err("server never answers");
+
+        assert_eq!(err, ApiError::Timeout);
+        assert_eq!(err.to_string(), "Request timed out");
+    }
+
+    #[tokio::test]
+    async fn retries_across_slow_mirrors_share_one_deadline() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    drop(socket);
+                });
+            }
+        });
+        let config = ApiConfig {
+            max_retries: 3,
+            request_timeout: Duration::from_millis(200),
+            total_timeout: Duration::from_millis(300),
+            ..NO_RETRIES
+        };
+        let client = build_client(&config).expect("client");
+        let slow = format!("http://{}", addr);
+        let pool = ServerPool::new(vec![slow.clone(), slow]).with_config(config);
+
+        let started = std::time::Instant::now();
+        let err = search_stations(&client, &pool, &SearchParams::default())
+            .await
+            .expect_err("server never answers");
+
+        assert_eq!(err, ApiError::Timeout);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let (server, hits) = scripted_server(&["404 Not Found"]).await;
+        let pool = ServerPool::new(vec![server]).with_config(ApiConfig {
+            max_retries: 3,
+            retry_delay: Duration::from_millis(1),
+            ..NO_RETRIES
+        });
+
+        let err = search_stations(&reqwest::Client::new(), &pool, &SearchParams::default())
+            .await
+            .expect_err("missing endpoint");
+
+        assert_eq!(err, ApiError::Http(reqwest::StatusCode::NOT_FOUND));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn api_errors_distinguish_parse_and_not_found() {
+        let garbage = mock_server("200 OK", "{not-json]").await;
+        let err = search_stations(
+            &reqwest::Client::new(),
+            &ServerPool::new(vec![garbage]),
+            &SearchParams::default(),
+        )
+        .await
+        .expect_err("invalid body");
+        assert!(matches!(err, ApiError::Parse(_)));
+
+        let error_object =
+            mock_server("200 OK", r#"{"ok":false,"message":"too many requests"}"#).await;
+        let err = search_stations(
+            &reqwest::Client::new(),
+            &ServerPool::new(vec![error_object]),
+            &SearchParams::default(),
+        )
+        .await
+        .expect_err("error object");
+        assert_eq!(err, ApiError::Api("too many requests".to_string()));
+        assert_eq!(err.to_string(), "API: too many requests");
+
+        let empty = mock_server("200 OK", "[]").await;
+        let err = fetch_station_by_uuid(
+            &reqwest::Client::new(),
+            &ServerPool::new(vec![empty]),
+            "gone",
+        )
+        .await
+        .expect_err("no station");
+        assert_eq!(err, ApiError::NotFound("gone".to_string()));
+        assert_eq!(err.to_string(), "Not found: gone");
+    }
+
+    #[tokio::test]
+    async fn connection_failures_are_network_errors() {
+        let pool = ServerPool::new(vec![dead_server().await]).with_config(NO_RETRIES);
+
+        let err = search_stations(&reqwest::Client::new(), &pool, &SearchParams::default())
+            .await
+            .expect_err("nothing listening");
+
+        assert!(matches!(err, ApiError::Network(_)));
+        assert!(err.to_string().starts_with("Request failed: "));
+    }
+
+    #[test]
+    fn stream_candidates_try_resolved_then_original_then_alternate_schemes() {
+        let mut station = station("a", 128);
+        station.url_resolved = "https://stream.example/live".to_string();
+        station.url = "http://stream.example/playlist.pls".to_string();
+
+        assert_eq!(
+            stream_candidates(&station, true),
+            vec![
+                "https://stream.example/live".to_string(),
+                "http://stream.example/playlist.pls".to_string(),
+                "http://stream.example/live".to_string(),
+                "https://stream.example/playlist.pls".to_string(),
+            ]
+        );
+        assert_eq!(
+            stream_candidates(&station, false),
+            vec![
+                "http://stream.example/playlist.pls".to_string(),
+                "https://stream.example/live".to_string(),
+                "https://stream.example/playlist.pls".to_string(),
+                "http://stream.example/live".to_string(),
+            ]
+        );
+
+        station.url_resolved = String::new();
+        station.url = "icy://stream.example".to_string();
+        assert_eq!(
+            stream_candidates(&station, true),
+            vec!["icy://stream.example".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn check_stream_skips_unreachable_urls() {
+        let dead = dead_server().await;
+        let healthy = mock_server("206 Partial Content", "").await;
+
+        let url = check_stream(&reqwest::Client::new(), &[dead, healthy.clone()])
+            .await
+            .expect("second url should answer");
+
+        assert_eq!(url, healthy);
+    }
+
+    #[tokio::test]
+    async fn check_stream_reports_last_failure() {
+        let missing = mock_server("404 Not Found", "").await;
+
+        let err = check_stream(&reqwest::Client::new(), &[missing])
+            .await
+            .expect_err("stream is missing");
+        assert_eq!(err, "Stream error: 404 Not Found");
+
+        let err = check_stream(&reqwest::Client::new(), &[])
+            .await
+            .expect_err("no urls");
+        assert_eq!(err, "Station has no stream URL");
+    }
+
+    #[test]
+    fn directory_defaults_to_radio_browser() {
+        let pool = ServerPool::default();
+        assert!(matches!(
+            Directory::from_settings(None, &pool),
+            Directory::RadioBrowser(_)
+        ));
+        assert!(matches!(
+            Directory::from_settings(Some("  "), &pool),
+            Directory::RadioBrowser(_)
+        ));
+        assert!(matches!(
+            Directory::from_settings(Some("https://radio.example/search"), &pool),
+            Directory::Custom(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn custom_directory_returns_stations_from_endpoint() {
+        let endpoint = mock_server(
+            "200 OK",
+            r#"[{"stationuuid":"a","name":"Alpha","url":"https://a","bitrate":64},{"stationuuid":"b","name":"Beta","url":"https://b","bitrate":192}]"#,
+        )
+        .await;
+        let directory = Directory::from_settings(Some(&endpoint), &ServerPool::default());
+        let params = SearchParams {
+            bitrate_min: Some(128),
+            ..SearchParams::default()
+        };
+
+        let (stations, fetched) = directory
+            .search(&reqwest::Client::new(), &params)
+            .await
+            .expect("custom directory should answer");
+
+        assert_eq!(fetched, 2);
+        assert_eq!(stations.len(), 1);
+        assert_eq!(stations[0].name, "Beta");
+    }
+
+    #[tokio::test]
+    async fn custom_directory_reports_http_errors() {
+        let endpoint = mock_server("404 Not Found", "").await;
+        let directory = Directory::from_settings(Some(&endpoint), &ServerPool::default());
+
+        let err = directory
+            .search(&reqwest::Client::new(), &SearchParams::default())
+            .await
+            .expect_err("missing endpoint");
+
+        assert_eq!(err, ApiError::Http(reqwest::StatusCode::NOT_FOUND));
+    }
 }
@@ -1,16 +1,36 @@
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crossterm::event::KeyCode;
+use tokio::task::JoinHandle;
 
 use crate::{
-    api::{SearchParams, Station},
+    api::{Directory, SearchParams, ServerPool, Station},
     favorites::FavoriteEntry,
+    keymap::{Action, KeyMap},
+    player::PlaybackState,
+    session::SessionSnapshot,
+    settings::{ConfirmQuit, Settings, Theme},
+    stats::{self, PlayStats},
 };
 
+pub const MAX_FILTER_LEN: usize = 100;
+const MOST_PLAYED_LIMIT: usize = 50;
+const HISTORY_LIMIT: usize = 50;
+const EARLY_FAILURE_WINDOW: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum InputField {
     Name,
     Country,
+    State,
     Language,
     Bitrate,
+    BitrateMax,
     Tags,
 }
 
@@ -18,12 +38,67 @@ pub enum InputField {
 pub enum AppMode {
     Normal,
     Filtering(InputField),
+    Command,
+    ConfirmQuit,
+    Help,
+    QuickFilter(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Name,
+    Bitrate,
+    Country,
+}
+
+impl SortKey {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Bitrate => "bitrate",
+            Self::Country => "country",
+        }
+    }
+
+    fn next(key: Option<Self>) -> Option<Self> {
+        match key {
+            None => Some(Self::Name),
+            Some(Self::Name) => Some(Self::Bitrate),
+            Some(Self::Bitrate) => Some(Self::Country),
+            Some(Self::Country) => None,
+        }
+    }
+
+    fn compare(self, a: &Station, b: &Station) -> std::cmp::Ordering {
+        match self {
+            Self::Name => a
+                .name
+                .trim()
+                .to_lowercase()
+                .cmp(&b.name.trim().to_lowercase()),
+            Self::Bitrate => a.bitrate.cmp(&b.bitrate),
+            Self::Country => a
+                .country_code
+                .to_uppercase()
+                .cmp(&b.country_code.to_uppercase()),
+        }
+    }
+
+    fn is_missing(self, station: &Station) -> bool {
+        match self {
+            Self::Name => station.name.trim().is_empty(),
+            Self::Bitrate => station.bitrate == 0,
+            Self::Country => station.country_code.trim().is_empty(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum StationViewMode {
     AllStations,
     Favorites,
+    MostPlayed,
+    History,
 }
 
 pub struct App {
@@ -35,22 +110,66 @@ pub struct App {
     pub view_mode: StationViewMode,
     pub params: SearchParams,
     pub page: u32,
+    pub loaded_page: u32,
     pub has_next_page: bool,
+    pub filtered_from: Option<usize>,
     pub loading: bool,
     pub favorites_loading: bool,
     pub error: Option<String>,
     pub favorites_error: Option<String>,
+    pub most_played_stations: Vec<Station>,
+    pub most_played_loading: bool,
+    pub history: Vec<Station>,
     pub playback_error: Option<String>,
+    pub status: Option<String>,
     pub current_station: Option<Station>,
+    pub last_station: Option<Station>,
+    pub playing_since: Option<Instant>,
+    pub playing_url: Option<String>,
+    pub retried_alternate: bool,
+    pub working_urls: HashMap<String, String>,
+    pub play_counts: HashMap<String, u32>,
+    pub stats: PlayStats,
+    pub stats_loaded: bool,
+    pub current_track: Option<String>,
+    pub recording: Option<PathBuf>,
+    pub paused: bool,
+    last_navigation: Option<(KeyCode, Instant)>,
+    quick_filter_origin: Option<(String, usize)>,
+    pub sort_key: Option<SortKey>,
+    pub sort_desc: bool,
+    api_order: HashMap<String, usize>,
     pub volume: u8,
     pub favorite_ids: HashSet<String>,
     pub favorites: Vec<FavoriteEntry>,
+    pub favorites_loaded: bool,
     pub latest_station_request_id: u64,
+    pub servers: ServerPool,
+    pub settings: Settings,
+    pub settings_loaded: bool,
+    pub keymap: KeyMap,
+    pub whats_new: Option<String>,
+    pub show_details: bool,
+    pub mini: bool,
+    pub monochrome: bool,
+    pub no_color: bool,
+    #[cfg(feature = "favicons")]
+    pub favicon: Option<(String, ratatui_image::protocol::Protocol)>,
     pub draft_name: String,
     pub draft_tags: String,
     pub draft_country: String,
+    pub draft_state: String,
     pub draft_language: String,
     pub draft_bitrate: String,
+    pub draft_bitrate_max: String,
+    pub command_input: String,
+    pub tasks: Vec<JoinHandle<()>>,
+    pub pending_favorite_updates: Vec<FavoriteEntry>,
+    pub favorite_limit_override: Option<String>,
+    pub checking_station: Option<Station>,
+    pub playback_state: PlaybackState,
+    pub spinner_frame: usize,
+    pub requests_in_flight: usize,
 }
 
 impl App {
@@ -64,22 +183,66 @@ impl App {
             view_mode: StationViewMode::AllStations,
             params: SearchParams::default(),
             page: 1,
+            loaded_page: 1,
             has_next_page: false,
+            filtered_from: None,
+            quick_filter_origin: None,
+            sort_key: None,
+            sort_desc: false,
+            api_order: HashMap::new(),
             loading: false,
             favorites_loading: false,
             error: None,
             favorites_error: None,
+            most_played_stations: Vec::new(),
+            most_played_loading: false,
+            history: Vec::new(),
             playback_error: None,
+            status: None,
             current_station: None,
+            last_station: None,
+            playing_since: None,
+            playing_url: None,
+            retried_alternate: false,
+            working_urls: HashMap::new(),
+            play_counts: HashMap::new(),
+            stats: PlayStats::new(),
+            stats_loaded: false,
+            current_track: None,
+            recording: None,
+            paused: false,
+            last_navigation: None,
             volume: 50,
             favorite_ids: HashSet::new(),
             favorites: Vec::new(),
+            favorites_loaded: false,
             latest_station_request_id: 0,
+            servers: ServerPool::default(),
+            settings: Settings::default(),
+            settings_loaded: false,
+            keymap: KeyMap::default(),
+            whats_new: None,
+            show_details: false,
+            mini: false,
+            monochrome: false,
+            no_color: false,
+            #[cfg(feature = "favicons")]
+            favicon: None,
             draft_name: String::new(),
             draft_tags: String::new(),
             draft_country: String::new(),
+            draft_state: String::new(),
             draft_language: String::new(),
             draft_bitrate: String::new(),
+            draft_bitrate_max: String::new(),
+            command_input: String::new(),
+            tasks: Vec::new(),
+            pending_favorite_updates: Vec::new(),
+            favorite_limit_override: None,
+            checking_station: None,
+            playback_state: PlaybackState::Stopped,
+            spinner_frame: 0,
+            requests_in_flight: 0,
         }
     }
 
@@ -88,24 +251,156 @@ impl App {
         self.favorites = favorites;
     }
 
+    pub fn apply_settings(&mut self, settings: Settings) -> Result<(), String> {
+        let keymap = KeyMap::from_bindings(&settings.keys);
+        self.settings = settings;
+        self.params.exact_tags = self.settings.exact_tags;
+        self.mini = self.settings.mini_layout;
+        self.monochrome = self.no_color || self.settings.theme == Theme::Mono;
+        self.order_favorites_first();
+        match keymap {
+            Ok(keymap) => {
+                self.keymap = keymap;
+                Ok(())
+            }
+            Err(err) => {
+                self.keymap = KeyMap::default();
+                Err(format!("{}. Using default keys.", err))
+            }
+        }
+    }
+
+    pub fn apply_default_filters(&mut self) {
+        if let Some(country) = &self.settings.default_country {
+            self.draft_country = country.clone();
+        }
+        if let Some(language) = &self.settings.default_language {
+            self.draft_language = language.clone();
+        }
+        self.update_params_from_drafts();
+    }
+
+    pub fn apply_saved_search(&mut self, name: &str) -> Result<(), String> {
+        let search = self
+            .settings
+            .saved_searches
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown saved search: {}", name))?;
+        self.draft_name = search.name;
+        self.draft_tags = search.tags;
+        self.draft_country = search.country;
+        self.draft_state = search.state;
+        self.draft_language = search.language;
+        self.draft_bitrate = search.bitrate.map(|b| b.to_string()).unwrap_or_default();
+        self.draft_bitrate_max = search
+            .bitrate_max
+            .map(|b| b.to_string())
+            .unwrap_or_default();
+        self.update_params_from_drafts();
+        self.params.codec = search.codec.trim().to_uppercase();
+        Ok(())
+    }
+
+    pub fn toggle_mini(&mut self) {
+        self.mini = !self.mini;
+        self.settings.mini_layout = self.mini;
+    }
+
+    pub fn toggle_prefer_resolved(&mut self) -> bool {
+        self.settings.prefer_resolved = !self.settings.prefer_resolved;
+        self.working_urls.clear();
+        self.settings.prefer_resolved
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.settings.theme = theme;
+        self.monochrome = self.no_color || theme == Theme::Mono;
+    }
+
+    pub fn station_source(&self) -> Directory {
+        Directory::from_settings(self.settings.custom_directory.as_deref(), &self.servers)
+    }
+
     pub fn update_params_from_drafts(&mut self) {
+        self.params.exact_tags = self.settings.exact_tags;
         self.params.name = self.draft_name.trim().to_string();
         self.params.tags = self.draft_tags.trim().to_string();
         self.params.country = self.draft_country.trim().to_uppercase();
+        self.params.state = self.draft_state.trim().to_string();
         self.params.language = self.draft_language.trim().to_lowercase();
-        self.params.bitrate = self.draft_bitrate.trim().parse::<u32>().ok();
+        self.params.bitrate_min = self.draft_bitrate.trim().parse::<u32>().ok();
+        self.params.bitrate_max = self.draft_bitrate_max.trim().parse::<u32>().ok();
+        if let (Some(min), Some(max)) = (self.params.bitrate_min, self.params.bitrate_max)
+            && min > max
+        {
+            self.params.bitrate_min = Some(max);
+            self.params.bitrate_max = Some(min);
+        }
         self.page = 1;
         self.params.offset = 0;
     }
 
+    #[cfg(test)]
     pub fn set_stations(&mut self, stations: Vec<Station>) {
-        let count = stations.len() as u32;
+        let fetched = stations.len();
+        self.set_search_results(stations, fetched);
+    }
+
+    pub fn set_search_results(&mut self, stations: Vec<Station>, fetched: usize) {
+        if stations.is_empty() && self.page > 1 && !self.stations.is_empty() {
+            self.page = self.loaded_page;
+            self.params.offset = (self.page - 1) * self.params.limit;
+            self.has_next_page = false;
+            self.loading = false;
+            self.error = None;
+            return;
+        }
+        if stations.is_empty() {
+            self.page = 1;
+            self.params.offset = 0;
+        }
+
+        self.filtered_from = (fetched > stations.len()).then_some(fetched);
+        self.loaded_page = self.page;
+        self.api_order = stations
+            .iter()
+            .enumerate()
+            .map(|(index, station)| (station.stationuuid.clone(), index))
+            .collect();
         self.stations = stations;
         self.selected = 0;
         self.scroll_offset = 0;
+        self.sort_stations();
+        self.order_favorites_first();
         self.loading = false;
         self.error = None;
-        self.has_next_page = count == self.params.limit;
+        self.has_next_page = fetched as u32 == self.params.limit;
+    }
+
+    fn order_favorites_first(&mut self) {
+        if !self.settings.favorites_first {
+            return;
+        }
+        let selected_uuid = self
+            .stations
+            .get(self.selected)
+            .map(|station| station.stationuuid.clone());
+        let (mut ordered, rest): (Vec<Station>, Vec<Station>) = std::mem::take(&mut self.stations)
+            .into_iter()
+            .partition(|station| self.favorite_ids.contains(&station.stationuuid));
+        ordered.extend(rest);
+        self.stations = ordered;
+
+        if self.view_mode == StationViewMode::AllStations
+            && let Some(uuid) = selected_uuid
+            && let Some(index) = self
+                .stations
+                .iter()
+                .position(|station| station.stationuuid == uuid)
+        {
+            self.selected = index;
+        }
     }
 
     pub fn note_station_request(&mut self) -> u64 {
@@ -125,6 +420,244 @@ impl App {
         self.scroll_offset = 0;
     }
 
+    pub fn reconcile_favorite_stations(&mut self, mut stations: Vec<Station>) {
+        stations.retain(|station| self.favorite_ids.contains(&station.stationuuid));
+        let selected_uuid = self
+            .favorite_stations
+            .get(self.selected)
+            .map(|station| station.stationuuid.clone());
+        self.favorite_stations = stations;
+        self.favorites_loading = false;
+        self.favorites_error = None;
+
+        if self.view_mode != StationViewMode::Favorites {
+            return;
+        }
+        self.selected = selected_uuid
+            .and_then(|uuid| {
+                self.favorite_stations
+                    .iter()
+                    .position(|station| station.stationuuid == uuid)
+            })
+            .unwrap_or(0);
+        self.clamp_selection();
+    }
+
+    pub fn forget_current_station(&mut self) -> Option<Station> {
+        self.playback_error = None;
+        self.checking_station = None;
+        self.last_station = None;
+        self.current_track = None;
+        self.current_station.take()
+    }
+
+    pub fn note_stream_dropped(&mut self) {
+        let Some(station) = self.current_station.take() else {
+            return;
+        };
+        self.playback_error = Some(format!(
+            "{} dropped. Press {} to reconnect",
+            station.name,
+            self.keymap.label(Action::Reconnect)
+        ));
+        self.last_station = Some(station);
+    }
+
+    pub fn note_stream_unavailable(&mut self) {
+        let Some(station) = self.current_station.take() else {
+            return;
+        };
+        self.playback_error = Some(format!(
+            "Stream unavailable: {}. Press {} to retry",
+            station.name,
+            self.keymap.label(Action::Reconnect)
+        ));
+        self.last_station = Some(station);
+    }
+
+    pub fn alternate_after_early_failure(&self, now: Instant) -> Option<(Station, String)> {
+        let station = self.current_station.as_ref()?;
+        let failed = self.playing_url.as_deref()?;
+        let started = self.playing_since?;
+        if self.retried_alternate
+            || now.saturating_duration_since(started) >= EARLY_FAILURE_WINDOW
+            || self.url_override(&station.stationuuid).is_some()
+        {
+            return None;
+        }
+        [&station.url_resolved, &station.url]
+            .into_iter()
+            .map(|url| url.trim())
+            .find(|url| !url.is_empty() && *url != failed)
+            .map(|url| (station.clone(), url.to_string()))
+    }
+
+    pub fn remember_working_url(&mut self, now: Instant) {
+        let (Some(station), Some(url), Some(started)) =
+            (&self.current_station, &self.playing_url, self.playing_since)
+        else {
+            return;
+        };
+        if now.saturating_duration_since(started) >= EARLY_FAILURE_WINDOW {
+            self.working_urls
+                .insert(station.stationuuid.clone(), url.clone());
+        }
+    }
+
+    pub fn record_play(&mut self, uuid: &str) {
+        *self.play_counts.entry(uuid.to_string()).or_default() += 1;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        stats::record_play(&mut self.stats, uuid, now);
+    }
+
+    pub fn push_history(&mut self, station: &Station) {
+        self.history
+            .retain(|entry| entry.stationuuid != station.stationuuid);
+        self.history.insert(0, station.clone());
+        self.history.truncate(HISTORY_LIMIT);
+        if self.view_mode == StationViewMode::History {
+            self.selected = 0;
+            self.scroll_offset = 0;
+        }
+    }
+
+    pub fn play_count(&self, uuid: &str) -> u32 {
+        self.play_counts.get(uuid).copied().unwrap_or_default()
+    }
+
+    pub fn total_play_count(&self, uuid: &str) -> u32 {
+        self.stats
+            .get(uuid)
+            .map(|entry| entry.plays)
+            .unwrap_or_default()
+    }
+
+    pub fn show_most_played(&mut self) -> Option<Vec<String>> {
+        self.set_view_mode(StationViewMode::MostPlayed);
+        let uuids = stats::most_played(&self.stats, MOST_PLAYED_LIMIT);
+        if uuids.is_empty() {
+            self.most_played_stations.clear();
+            return None;
+        }
+        self.most_played_loading = true;
+        Some(uuids)
+    }
+
+    pub fn set_most_played_stations(&mut self, found: Vec<(String, Station)>) {
+        let mut ranked: Vec<(u32, u64, Station)> = found
+            .into_iter()
+            .map(|(uuid, station)| {
+                let entry = self.stats.get(&uuid).cloned().unwrap_or_default();
+                (entry.plays, entry.last_played, station)
+            })
+            .collect();
+        ranked.sort_by_key(|(plays, last_played, _)| std::cmp::Reverse((*plays, *last_played)));
+        self.most_played_stations = ranked.into_iter().map(|(_, _, station)| station).collect();
+        self.most_played_loading = false;
+        if self.view_mode == StationViewMode::MostPlayed {
+            self.selected = 0;
+            self.scroll_offset = 0;
+        }
+    }
+
+    pub fn allow_navigation(&mut self, code: KeyCode, now: Instant) -> bool {
+        if !matches!(code, KeyCode::Up | KeyCode::Down) {
+            return true;
+        }
+        let interval = self.settings.nav_repeat();
+        let throttled = self
+            .last_navigation
+            .is_some_and(|(last, at)| last == code && now.saturating_duration_since(at) < interval);
+        if !throttled {
+            self.last_navigation = Some((code, now));
+        }
+        !throttled
+    }
+
+    pub fn request_quit(&mut self) -> bool {
+        let confirm = match self.settings.confirm_quit {
+            ConfirmQuit::Never => false,
+            ConfirmQuit::Playing => self.current_station.is_some(),
+            ConfirmQuit::Always => true,
+        };
+        if confirm {
+            self.mode = AppMode::ConfirmQuit;
+        }
+        !confirm
+    }
+
+    pub fn reconnect_station(&self) -> Option<Station> {
+        self.current_station
+            .clone()
+            .or_else(|| self.last_station.clone())
+    }
+
+    pub fn remap_favorite_uuids(&mut self, responses: &[(String, Station)]) -> usize {
+        let mut remapped = 0;
+        for (requested, station) in responses {
+            if *requested == station.stationuuid {
+                continue;
+            }
+            let Some(index) = self
+                .favorites
+                .iter()
+                .position(|fav| fav.stationuuid == *requested)
+            else {
+                continue;
+            };
+            self.favorite_ids.remove(requested);
+            if self.favorite_ids.insert(station.stationuuid.clone()) {
+                self.favorites[index].stationuuid = station.stationuuid.clone();
+            } else {
+                self.favorites.remove(index);
+            }
+            remapped += 1;
+        }
+        remapped
+    }
+
+    pub fn apply_favorite_updates(&mut self) -> usize {
+        let updates = std::mem::take(&mut self.pending_favorite_updates);
+        let mut applied = 0;
+        for update in updates {
+            if let Some(entry) = self
+                .favorites
+                .iter_mut()
+                .find(|fav| fav.stationuuid == update.stationuuid)
+            {
+                *entry = update;
+                applied += 1;
+            }
+        }
+        applied
+    }
+
+    pub fn tick(&mut self) {
+        self.spinner_frame = self.spinner_frame.wrapping_add(1);
+    }
+
+    pub fn begin_request(&mut self) {
+        self.requests_in_flight += 1;
+    }
+
+    pub fn finish_request(&mut self) {
+        self.requests_in_flight = self.requests_in_flight.saturating_sub(1);
+    }
+
+    pub fn track_task(&mut self, task: JoinHandle<()>) {
+        self.tasks.retain(|task| !task.is_finished());
+        self.tasks.push(task);
+    }
+
+    pub fn abort_tasks(&mut self) {
+        for task in self.tasks.drain(..) {
+            task.abort();
+        }
+    }
+
     pub fn set_error(&mut self, err: String) {
         self.error = Some(err);
         self.loading = false;
@@ -136,10 +669,104 @@ impl App {
         self.favorites_loading = false;
     }
 
+    pub fn set_status(&mut self, status: String) {
+        self.status = Some(status);
+    }
+
+    pub fn session_snapshot(&self) -> SessionSnapshot {
+        SessionSnapshot::from_state(
+            &self.params,
+            &self.view_mode,
+            self.current_station
+                .as_ref()
+                .map(|station| station.stationuuid.as_str()),
+        )
+    }
+
+    pub fn apply_session(&mut self, snapshot: &SessionSnapshot) {
+        self.draft_name = snapshot.name.clone();
+        self.draft_tags = snapshot.tags.clone();
+        self.draft_country = snapshot.country.clone();
+        self.draft_state = snapshot.state.clone();
+        self.draft_language = snapshot.language.clone();
+        self.draft_bitrate = snapshot
+            .bitrate
+            .map(|bitrate| bitrate.to_string())
+            .unwrap_or_default();
+        self.draft_bitrate_max = snapshot
+            .bitrate_max
+            .map(|bitrate| bitrate.to_string())
+            .unwrap_or_default();
+        self.update_params_from_drafts();
+        self.params.codec = snapshot.codec.clone();
+        self.mode = AppMode::Normal;
+        self.set_view_mode(snapshot.view_mode.clone());
+    }
+
+    pub fn find_similar_to_selected(&mut self) -> Result<String, String> {
+        let station = self
+            .selected_station()
+            .cloned()
+            .ok_or_else(|| "No station selected".to_string())?;
+        let tag = station
+            .tags
+            .split(',')
+            .map(str::trim)
+            .find(|tag| !tag.is_empty())
+            .unwrap_or_default()
+            .to_string();
+        if tag.is_empty() && station.country_code.trim().is_empty() {
+            return Err(format!(
+                "No tags or country known for {}. Press {} to refresh favorites first",
+                station.name,
+                self.keymap.label(Action::RefreshFavorites)
+            ));
+        }
+
+        self.draft_name.clear();
+        self.draft_state.clear();
+        self.draft_language.clear();
+        self.draft_bitrate.clear();
+        self.draft_bitrate_max.clear();
+        self.draft_tags = tag;
+        self.draft_country = station.country_code.trim().to_string();
+        self.update_params_from_drafts();
+        self.set_view_mode(StationViewMode::AllStations);
+        self.loading = true;
+        Ok(station.name)
+    }
+
+    pub fn now_playing_text(&self) -> Option<String> {
+        let station = self.current_station.as_ref()?;
+        Some(
+            self.current_track
+                .as_deref()
+                .map(str::trim)
+                .filter(|track| !track.is_empty())
+                .unwrap_or(&station.name)
+                .to_string(),
+        )
+    }
+
+    pub fn window_title(&self, now: Instant) -> String {
+        let Some(station) = &self.current_station else {
+            return "cradio".to_string();
+        };
+        let mut parts = vec![station.name.clone()];
+        if station.bitrate > 0 {
+            parts.push(format!("{} kbps", station.bitrate));
+        }
+        if let Some(since) = self.playing_since {
+            parts.push(format_elapsed(now.saturating_duration_since(since)));
+        }
+        format!("{} - cradio", parts.join(" · "))
+    }
+
     pub fn active_error(&self) -> Option<&str> {
         match self.view_mode {
             StationViewMode::AllStations => self.error.as_deref(),
             StationViewMode::Favorites => self.favorites_error.as_deref(),
+            StationViewMode::MostPlayed | StationViewMode::History => None,
         }
     }
 
@@ -151,6 +778,8 @@ impl App {
         match self.view_mode {
             StationViewMode::AllStations => &self.stations,
             StationViewMode::Favorites => &self.favorite_stations,
+            StationViewMode::MostPlayed => &self.most_played_stations,
+            StationViewMode::History => &self.history,
         }
     }
 
@@ -162,8 +791,95 @@ impl App {
         self.favorite_ids.contains(stationuuid)
     }
 
-    pub fn toggle_favorite_for_selected(&mut self) -> Option<bool> {
-        let station = self.selected_station()?.clone();
+    pub fn url_override(&self, stationuuid: &str) -> Option<&str> {
+        self.favorites
+            .iter()
+            .find(|fav| fav.stationuuid == stationuuid)
+            .and_then(|fav| fav.url_override.as_deref())
+    }
+
+    pub fn playback_url(&self, station: &Station) -> String {
+        let (first, second) = if self.settings.prefer_resolved {
+            (&station.url_resolved, &station.url)
+        } else {
+            (&station.url, &station.url_resolved)
+        };
+        if let Some(url) = self.url_override(&station.stationuuid) {
+            url.to_string()
+        } else if let Some(url) = self.working_urls.get(&station.stationuuid) {
+            url.clone()
+        } else if !first.is_empty() {
+            first.clone()
+        } else {
+            second.clone()
+        }
+    }
+
+    pub fn set_url_override_for_selected(&mut self, url: Option<String>) -> Result<String, String> {
+        let station = self
+            .selected_station()
+            .ok_or_else(|| "No station selected".to_string())?;
+        let stationuuid = station.stationuuid.clone();
+        let entry = self
+            .favorites
+            .iter_mut()
+            .find(|fav| fav.stationuuid == stationuuid)
+            .ok_or_else(|| "Only favorites can have a custom URL".to_string())?;
+        entry.url_override = url;
+        Ok(entry.name.clone())
+    }
+
+    pub fn check_favorite_limit(&mut self, index: usize) -> Result<(), String> {
+        let Some(station) = self.current_station_list().get(index) else {
+            return Ok(());
+        };
+        let Some(limit) = self.settings.max_favorites else {
+            return Ok(());
+        };
+        if self.favorite_ids.contains(&station.stationuuid) || self.favorites.len() < limit {
+            return Ok(());
+        }
+
+        let uuid = station.stationuuid.clone();
+        if self.favorite_limit_override.as_deref() == Some(uuid.as_str()) {
+            self.favorite_limit_override = None;
+            return Ok(());
+        }
+        self.favorite_limit_override = Some(uuid);
+        Err(format!(
+            "Favorites limit ({}) reached. Press again to add anyway, or remove some first.",
+            limit
+        ))
+    }
+
+    pub fn cap_imported_favorites(&self, merged: &mut Vec<FavoriteEntry>) -> usize {
+        let Some(limit) = self.settings.max_favorites else {
+            return 0;
+        };
+        let keep = limit.max(self.favorites.len());
+        let skipped = merged.len().saturating_sub(keep);
+        merged.truncate(keep);
+        skipped
+    }
+
+    pub fn favorites_limit_warning(&self) -> Option<String> {
+        let limit = self.settings.max_favorites?;
+        (self.favorites.len() > limit).then(|| {
+            format!(
+                "{} favorites stored, above the limit of {}. Consider pruning the list.",
+                self.favorites.len(),
+                limit
+            )
+        })
+    }
+
+    pub fn visible_row_index(&self, first_visible: usize, row: usize) -> Option<usize> {
+        let index = first_visible + row;
+        (index < self.current_station_list().len()).then_some(index)
+    }
+
+    pub fn toggle_favorite_at(&mut self, index: usize) -> Option<bool> {
+        let station = self.current_station_list().get(index)?.clone();
         let now_favorite = if self.favorite_ids.contains(&station.stationuuid) {
             self.favorite_ids.remove(&station.stationuuid);
             self.favorites
@@ -183,6 +899,7 @@ impl App {
                     stationuuid: station.stationuuid.clone(),
                     name: station.name.clone(),
                     url: station.url.clone(),
+                    url_override: None,
                 });
             }
             true
@@ -191,24 +908,142 @@ impl App {
         if self.view_mode == StationViewMode::Favorites && !now_favorite {
             self.favorite_stations
                 .retain(|s| s.stationuuid != station.stationuuid);
-            if self.selected >= self.favorite_stations.len() {
-                self.selected = self.favorite_stations.len().saturating_sub(1);
-            }
-            if self.scroll_offset > self.selected {
-                self.scroll_offset = self.selected;
+            if index < self.selected {
+                self.selected -= 1;
             }
+            self.clamp_selection();
         }
 
         Some(now_favorite)
     }
 
+    pub fn show_cached_favorites(&mut self) {
+        self.set_view_mode(StationViewMode::Favorites);
+        self.favorites_error = None;
+        let mut cached: Vec<Station> = self
+            .favorites
+            .iter()
+            .map(FavoriteEntry::to_cached_station)
+            .collect();
+        cached.sort_by_cached_key(|s| s.name.to_lowercase());
+        self.set_favorite_stations(cached);
+    }
+
+    pub fn refresh_favorites(&mut self) -> Option<Vec<String>> {
+        self.show_cached_favorites();
+        if self.favorites.is_empty() {
+            return None;
+        }
+        self.favorites_loading = true;
+        Some(
+            self.favorites
+                .iter()
+                .map(|fav| fav.stationuuid.clone())
+                .collect(),
+        )
+    }
+
     pub fn set_view_mode(&mut self, mode: StationViewMode) {
         self.view_mode = mode;
         self.selected = 0;
         self.scroll_offset = 0;
     }
 
+    pub fn clamp_selection(&mut self) {
+        let last = self.current_station_list().len().saturating_sub(1);
+        self.selected = self.selected.min(last);
+        self.scroll_offset = self.scroll_offset.min(self.selected);
+    }
+
+    pub fn keep_selection_visible(&mut self, visible_height: usize) {
+        self.clamp_selection();
+        if self.selected < self.scroll_offset {
+            self.scroll_offset = self.selected;
+        } else if visible_height > 0 && self.selected >= self.scroll_offset + visible_height {
+            self.scroll_offset = self.selected + 1 - visible_height;
+        }
+    }
+
+    pub fn quick_filter(&self) -> Option<&str> {
+        match &self.mode {
+            AppMode::QuickFilter(query) => Some(query),
+            _ => None,
+        }
+    }
+
+    pub fn quick_filtered_indices(&self) -> Vec<usize> {
+        let query = self.quick_filter().unwrap_or_default();
+        self.current_station_list()
+            .iter()
+            .enumerate()
+            .filter(|(_, station)| quick_filter_match(&station.name, query).is_some())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    pub fn start_quick_filter(&mut self) {
+        self.quick_filter_origin = self
+            .selected_station()
+            .map(|station| (station.stationuuid.clone(), self.scroll_offset));
+        self.mode = AppMode::QuickFilter(String::new());
+    }
+
+    pub fn push_quick_filter_char(&mut self, c: char) {
+        if let AppMode::QuickFilter(query) = &mut self.mode {
+            query.push(c);
+            self.snap_to_quick_filter();
+        }
+    }
+
+    pub fn pop_quick_filter_char(&mut self) {
+        if let AppMode::QuickFilter(query) = &mut self.mode {
+            query.pop();
+            self.snap_to_quick_filter();
+        }
+    }
+
+    fn snap_to_quick_filter(&mut self) {
+        let matches = self.quick_filtered_indices();
+        if !matches.contains(&self.selected)
+            && let Some(first) = matches.first()
+        {
+            self.selected = *first;
+        }
+    }
+
+    pub fn cancel_quick_filter(&mut self) {
+        self.mode = AppMode::Normal;
+        if let Some((uuid, scroll_offset)) = self.quick_filter_origin.take()
+            && let Some(index) = self
+                .current_station_list()
+                .iter()
+                .position(|station| station.stationuuid == uuid)
+        {
+            self.selected = index;
+            self.scroll_offset = scroll_offset.min(index);
+        }
+        self.clamp_selection();
+    }
+
+    pub fn accept_quick_filter(&mut self) -> bool {
+        let matched = self.quick_filtered_indices().contains(&self.selected);
+        self.quick_filter_origin = None;
+        self.mode = AppMode::Normal;
+        self.clamp_selection();
+        matched
+    }
+
     pub fn select_next(&mut self, visible_height: usize) {
+        if self.quick_filter().is_some() {
+            if let Some(next) = self
+                .quick_filtered_indices()
+                .into_iter()
+                .find(|index| *index > self.selected)
+            {
+                self.selected = next;
+            }
+            return;
+        }
         let station_count = self.current_station_list().len();
         if station_count == 0 {
             return;
@@ -222,6 +1057,16 @@ impl App {
     }
 
     pub fn select_prev(&mut self) {
+        if self.quick_filter().is_some() {
+            if let Some(prev) = self
+                .quick_filtered_indices()
+                .into_iter()
+                .rfind(|index| *index < self.selected)
+            {
+                self.selected = prev;
+            }
+            return;
+        }
         if self.current_station_list().is_empty() {
             return;
         }
@@ -261,25 +1106,127 @@ impl App {
         false
     }
 
+    pub fn go_to_page(&mut self, page: u32) -> bool {
+        let Some(offset) = page
+            .checked_sub(1)
+            .and_then(|index| index.checked_mul(self.params.limit))
+        else {
+            return false;
+        };
+        self.page = page;
+        self.params.offset = offset;
+        true
+    }
+
+    pub fn cycle_codec(&mut self) {
+        let next = match self.params.codec.as_str() {
+            "" => "MP3",
+            "MP3" => "AAC",
+            "AAC" => "OGG",
+            _ => "",
+        };
+        self.params.codec = next.to_string();
+        self.page = 1;
+        self.params.offset = 0;
+        self.loading = true;
+    }
+
+    pub fn cycle_sort(&mut self) {
+        self.sort_key = SortKey::next(self.sort_key);
+        self.sort_desc = self.sort_key == Some(SortKey::Bitrate);
+        let selected_uuid = self
+            .stations
+            .get(self.selected)
+            .map(|station| station.stationuuid.clone());
+        self.sort_stations();
+        self.order_favorites_first();
+        if self.view_mode == StationViewMode::AllStations
+            && let Some(uuid) = selected_uuid
+            && let Some(index) = self
+                .stations
+                .iter()
+                .position(|station| station.stationuuid == uuid)
+        {
+            self.selected = index;
+            self.scroll_offset = self.scroll_offset.min(index);
+        }
+    }
+
+    fn sort_stations(&mut self) {
+        let api_order = &self.api_order;
+        self.stations.sort_by_key(|station| {
+            api_order
+                .get(&station.stationuuid)
+                .copied()
+                .unwrap_or(usize::MAX)
+        });
+        let Some(key) = self.sort_key else {
+            return;
+        };
+        let desc = self.sort_desc;
+        self.stations.sort_by(|a, b| {
+            key.is_missing(a).cmp(&key.is_missing(b)).then_with(|| {
+                let order = key.compare(a, b);
+                if desc { order.reverse() } else { order }
+            })
+        });
+    }
+
+    pub fn toggle_reverse(&mut self) {
+        self.params.reverse = !self.params.reverse;
+        self.page = 1;
+        self.params.offset = 0;
+        self.loading = true;
+    }
+
+    pub fn push_filter_char(&mut self, c: char) -> bool {
+        let bitrate_only = matches!(
+            self.mode,
+            AppMode::Filtering(InputField::Bitrate | InputField::BitrateMax)
+        );
+        if bitrate_only && !c.is_ascii_digit() {
+            return true;
+        }
+        match self.active_field_mut() {
+            Some(field) if field.chars().count() >= MAX_FILTER_LEN => false,
+            Some(field) => {
+                field.push(c);
+                true
+            }
+            None => true,
+        }
+    }
+
     pub fn active_field_mut(&mut self) -> Option<&mut String> {
         match &self.mode {
             AppMode::Filtering(InputField::Name) => Some(&mut self.draft_name),
             AppMode::Filtering(InputField::Country) => Some(&mut self.draft_country),
+            AppMode::Filtering(InputField::State) => Some(&mut self.draft_state),
             AppMode::Filtering(InputField::Language) => Some(&mut self.draft_language),
             AppMode::Filtering(InputField::Bitrate) => Some(&mut self.draft_bitrate),
+            AppMode::Filtering(InputField::BitrateMax) => Some(&mut self.draft_bitrate_max),
             AppMode::Filtering(InputField::Tags) => Some(&mut self.draft_tags),
-            AppMode::Normal => None,
+            AppMode::Command => Some(&mut self.command_input),
+            AppMode::Normal | AppMode::ConfirmQuit | AppMode::Help | AppMode::QuickFilter(_) => {
+                None
+            }
         }
     }
 
     pub fn next_field(&mut self) {
         self.mode = match &self.mode {
             AppMode::Filtering(InputField::Name) => AppMode::Filtering(InputField::Country),
-            AppMode::Filtering(InputField::Country) => AppMode::Filtering(InputField::Language),
+            AppMode::Filtering(InputField::Country) => AppMode::Filtering(InputField::State),
+            AppMode::Filtering(InputField::State) => AppMode::Filtering(InputField::Language),
             AppMode::Filtering(InputField::Language) => AppMode::Filtering(InputField::Tags),
             AppMode::Filtering(InputField::Tags) => AppMode::Filtering(InputField::Bitrate),
-            AppMode::Filtering(InputField::Bitrate) => AppMode::Filtering(InputField::Name),
+            AppMode::Filtering(InputField::Bitrate) => AppMode::Filtering(InputField::BitrateMax),
+            AppMode::Filtering(InputField::BitrateMax) => AppMode::Filtering(InputField::Name),
             AppMode::Normal => AppMode::Normal,
+            AppMode::Command => AppMode::Command,
+            AppMode::ConfirmQuit => AppMode::ConfirmQuit,
+            AppMode::Help => AppMode::Help,
+            AppMode::QuickFilter(query) => AppMode::QuickFilter(query.clone()),
         };
     }
 
@@ -295,19 +1242,102 @@ impl App {
                 } else {
                     " - end reached"
                 };
-                format!(" Stations - Page {}{} ", self.page, suffix)
+                let direction = if self.params.reverse { "▼" } else { "▲" };
+                let position = if !self.settings.show_range {
+                    format!("Page {}", self.page)
+                } else if self.stations.is_empty() {
+                    "No results".to_string()
+                } else {
+                    format!(
+                        "Showing {}–{}",
+                        self.params.offset + 1,
+                        self.params.offset + self.stations.len() as u32
+                    )
+                };
+                let matching = self
+                    .filtered_from
+                    .map(|fetched| format!(" - {} of {} matching", self.stations.len(), fetched))
+                    .unwrap_or_default();
+                let sort = self
+                    .sort_key
+                    .map(|key| {
+                        let arrow = if self.sort_desc { "▼" } else { "▲" };
+                        format!(" - by {} {}", key.label(), arrow)
+                    })
+                    .unwrap_or_default();
+                let codec = if self.params.codec.is_empty() {
+                    String::new()
+                } else {
+                    format!(" - {} only", self.params.codec)
+                };
+                format!(
+                    " Stations {} - {}{}{}{}{} ",
+                    direction, position, matching, codec, sort, suffix
+                )
+            }
+            StationViewMode::Favorites if self.favorites_loading => {
+                " Favorites - refreshing... ".to_string()
             }
             StationViewMode::Favorites => " Favorites ".to_string(),
+            StationViewMode::MostPlayed if self.most_played_loading => {
+                " Most Played - loading... ".to_string()
+            }
+            StationViewMode::MostPlayed => " Most Played ".to_string(),
+            StationViewMode::History => " History ".to_string(),
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::{App, StationViewMode};
-    use crate::api::Station;
+pub fn quick_filter_match(name: &str, query: &str) -> Option<Range<usize>> {
+    let needle: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if needle.is_empty() {
+        return Some(0..0);
+    }
+    name.char_indices().find_map(|(start, _)| {
+        let mut expected = needle.iter();
+        let mut next = expected.next();
+        for (offset, c) in name[start..].char_indices() {
+            for lower in c.to_lowercase() {
+                match next {
+                    Some(wanted) if *wanted == lower => next = expected.next(),
+                    Some(_) => return None,
+                    None => break,
+                }
+            }
+            if next.is_none() {
+                return Some(start..start + offset + c.len_utf8());
+            }
+        }
+        None
+    })
+}
 
-    fn station(uuid: &str, name: &str, url: &str) -> Station {
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        App, AppMode, InputField, MAX_FILTER_LEN, SortKey, StationViewMode, quick_filter_match,
+    };
+    use crate::{
+        api::Station,
+        favorites::FavoriteEntry,
+        keymap::{Action, KeyMap},
+        session::SessionSnapshot,
+        settings::{ConfirmQuit, SavedSearch, Settings, Theme},
+        stats::StationStats,
+    };
+    use crossterm::event::KeyCode;
+    use std::time::{Duration, Instant};
+
+    fn station(uuid: &str, name: &str, url: &str) -> Station {
         Station {
             stationuuid: uuid.to_string(),
             name: name.to_string(),
@@ -317,6 +1347,10 @@ mod tests {
             country_code: "".to_string(),
             language: "".to_string(),
             bitrate: 0,
+            favicon: String::new(),
+            votes: 0,
+            codec: String::new(),
+            homepage: String::new(),
         }
     }
 
@@ -325,14 +1359,14 @@ mod tests {
         let mut app = App::new();
         app.stations = vec![station("id-1", "One", "https://one")];
 
-        let added = app.toggle_favorite_for_selected();
+        let added = app.toggle_favorite_at(app.selected);
         assert_eq!(added, Some(true));
         assert!(app.is_favorite("id-1"));
         assert_eq!(app.favorites.len(), 1);
         assert_eq!(app.favorites[0].name, "One");
         assert_eq!(app.favorites[0].url, "https://one");
 
-        let removed = app.toggle_favorite_for_selected();
+        let removed = app.toggle_favorite_at(app.selected);
         assert_eq!(removed, Some(false));
         assert!(!app.is_favorite("id-1"));
         assert!(app.favorites.is_empty());
@@ -342,11 +1376,11 @@ mod tests {
     fn re_favorite_updates_stored_name_and_url() {
         let mut app = App::new();
         app.stations = vec![station("id-1", "Old", "https://old")];
-        let _ = app.toggle_favorite_for_selected();
-        let _ = app.toggle_favorite_for_selected();
+        let _ = app.toggle_favorite_at(app.selected);
+        let _ = app.toggle_favorite_at(app.selected);
 
         app.stations = vec![station("id-1", "New", "https://new")];
-        let added = app.toggle_favorite_for_selected();
+        let added = app.toggle_favorite_at(app.selected);
 
         assert_eq!(added, Some(true));
         assert_eq!(app.favorites.len(), 1);
@@ -364,6 +1398,107 @@ mod tests {
         assert_eq!(app.view_mode, StationViewMode::AllStations);
     }
 
+    #[test]
+    fn plays_are_counted_per_station() {
+        let mut app = App::new();
+        assert_eq!(app.play_count("id-1"), 0);
+
+        app.record_play("id-1");
+        app.record_play("id-2");
+        app.record_play("id-1");
+
+        assert_eq!(app.play_count("id-1"), 2);
+        assert_eq!(app.play_count("id-2"), 1);
+        assert_eq!(app.play_count("id-3"), 0);
+        assert_eq!(app.total_play_count("id-1"), 2);
+    }
+
+    #[test]
+    fn most_played_view_ranks_loaded_stations_by_play_count() {
+        let mut app = App::new();
+        assert_eq!(app.show_most_played(), None);
+        assert_eq!(app.view_mode, StationViewMode::MostPlayed);
+        assert!(!app.most_played_loading);
+
+        app.stats.insert(
+            "id-1".to_string(),
+            StationStats {
+                plays: 3,
+                last_played: 10,
+            },
+        );
+        app.stats.insert(
+            "id-2".to_string(),
+            StationStats {
+                plays: 7,
+                last_played: 5,
+            },
+        );
+        assert_eq!(
+            app.show_most_played(),
+            Some(vec!["id-2".to_string(), "id-1".to_string()])
+        );
+        assert!(app.stations_title().contains("Most Played - loading..."));
+
+        app.set_most_played_stations(vec![
+            ("id-1".to_string(), station("id-1", "One", "https://one")),
+            (
+                "id-2".to_string(),
+                station("id-2-new", "Two", "https://two"),
+            ),
+        ]);
+
+        assert!(!app.most_played_loading);
+        let names: Vec<&str> = app
+            .current_station_list()
+            .iter()
+            .map(|station| station.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Two", "One"]);
+        assert_eq!(app.stations_title(), " Most Played ");
+    }
+
+    #[test]
+    fn clearing_the_quick_filter_restores_the_previously_selected_station() {
+        let mut app = App::new();
+        app.stations = (1..=30)
+            .map(|i| {
+                station(
+                    &format!("id-{}", i),
+                    &format!("Station {}", i),
+                    "https://one",
+                )
+            })
+            .collect();
+        app.selected = 24;
+        app.scroll_offset = 12;
+
+        app.start_quick_filter();
+        app.push_quick_filter_char('3');
+        assert_eq!(app.selected, 2);
+        app.select_next(0);
+        assert_eq!(app.selected, 12);
+        app.stations.remove(0);
+        app.cancel_quick_filter();
+        assert_eq!(
+            app.selected_station().map(|s| s.stationuuid.as_str()),
+            Some("id-25")
+        );
+        assert_eq!((app.selected, app.scroll_offset), (23, 12));
+
+        app.start_quick_filter();
+        app.push_quick_filter_char('7');
+        assert_eq!(app.selected, 5);
+        app.stations
+            .retain(|station| station.stationuuid != "id-25");
+        app.cancel_quick_filter();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.selected_station().map(|s| s.stationuuid.as_str()),
+            Some("id-7")
+        );
+    }
+
     #[test]
     fn removing_favorite_in_favorites_view_updates_selection_safely() {
         let mut app = App::new();
@@ -371,15 +1506,15 @@ mod tests {
         let second = station("id-2", "Two", "https://two");
 
         app.stations = vec![first.clone(), second.clone()];
-        let _ = app.toggle_favorite_for_selected();
+        let _ = app.toggle_favorite_at(app.selected);
         app.selected = 1;
-        let _ = app.toggle_favorite_for_selected();
+        let _ = app.toggle_favorite_at(app.selected);
 
         app.favorite_stations = vec![first, second];
         app.set_view_mode(StationViewMode::Favorites);
         app.selected = 1;
 
-        let removed = app.toggle_favorite_for_selected();
+        let removed = app.toggle_favorite_at(app.selected);
 
         assert_eq!(removed, Some(false));
         assert_eq!(app.favorite_stations.len(), 1);
@@ -387,6 +1522,99 @@ mod tests {
         assert_eq!(app.favorite_stations[0].stationuuid, "id-1");
     }
 
+    #[test]
+    fn clamp_selection_keeps_selection_inside_the_active_list() {
+        let mut app = App::new();
+        app.selected = 3;
+        app.scroll_offset = 2;
+        app.clamp_selection();
+        assert_eq!((app.selected, app.scroll_offset), (0, 0));
+
+        app.stations = vec![
+            station("id-1", "One", "https://one"),
+            station("id-2", "Two", "https://two"),
+            station("id-3", "Three", "https://three"),
+        ];
+        app.selected = 2;
+        app.scroll_offset = 1;
+        app.clamp_selection();
+        assert_eq!((app.selected, app.scroll_offset), (2, 1));
+
+        app.selected = 3;
+        app.scroll_offset = 3;
+        app.clamp_selection();
+        assert_eq!((app.selected, app.scroll_offset), (2, 2));
+        assert_eq!(
+            app.selected_station().map(|s| s.stationuuid.as_str()),
+            Some("id-3")
+        );
+    }
+
+    #[test]
+    fn keep_selection_visible_scrolls_to_the_selected_row() {
+        let mut app = App::new();
+        app.stations = (0..20)
+            .map(|i| station(&i.to_string(), "Station", "https://s"))
+            .collect();
+        app.selected = 12;
+        app.keep_selection_visible(5);
+        assert_eq!(app.scroll_offset, 8);
+
+        app.selected = 3;
+        app.keep_selection_visible(5);
+        assert_eq!(app.scroll_offset, 3);
+
+        app.keep_selection_visible(10);
+        assert_eq!(app.scroll_offset, 3);
+    }
+
+    #[test]
+    fn history_is_most_recent_first_deduplicated_and_capped() {
+        let mut app = App::new();
+        let one = station("id-1", "One", "https://one");
+        let two = station("id-2", "Two", "https://two");
+        app.push_history(&one);
+        app.push_history(&two);
+        app.push_history(&one);
+
+        let ids: Vec<&str> = app.history.iter().map(|s| s.stationuuid.as_str()).collect();
+        assert_eq!(ids, vec!["id-1", "id-2"]);
+
+        for i in 0..60 {
+            app.push_history(&station(&format!("more-{}", i), "More", "https://more"));
+        }
+        assert_eq!(app.history.len(), 50);
+        assert_eq!(app.history[0].stationuuid, "more-59");
+    }
+
+    #[test]
+    fn history_survives_switching_views() {
+        let mut app = App::new();
+        app.push_history(&station("id-1", "One", "https://one"));
+        app.set_view_mode(StationViewMode::Favorites);
+        app.set_view_mode(StationViewMode::AllStations);
+        app.set_view_mode(StationViewMode::History);
+
+        assert_eq!(app.current_station_list().len(), 1);
+        assert_eq!(app.stations_title(), " History ");
+        assert_eq!(app.active_error(), None);
+    }
+
+    #[test]
+    fn removing_the_last_favorite_leaves_a_valid_selection() {
+        let mut app = App::new();
+        let only = station("id-1", "One", "https://one");
+        app.stations = vec![only.clone()];
+        let _ = app.toggle_favorite_at(app.selected);
+        app.favorite_stations = vec![only];
+        app.set_view_mode(StationViewMode::Favorites);
+
+        assert_eq!(app.toggle_favorite_at(app.selected), Some(false));
+
+        assert!(app.favorite_stations.is_empty());
+        assert_eq!((app.selected, app.scroll_offset), (0, 0));
+    }
+
     #[test]
     fn favorites_errors_stay_scoped_to_favorites_view() {
         let mut app = App::new();
@@ -405,10 +1633,91 @@ mod tests {
         let mut app = App::new();
         app.page = 3;
         app.has_next_page = true;
-        assert_eq!(app.stations_title(), " Stations - Page 3 - more available ");
+        assert_eq!(
+            app.stations_title(),
+            " Stations ▼ - Page 3 - more available "
+        );
 
         app.has_next_page = false;
-        assert_eq!(app.stations_title(), " Stations - Page 3 - end reached ");
+        assert_eq!(app.stations_title(), " Stations ▼ - Page 3 - end reached ");
+    }
+
+    fn full_page(app: &App) -> Vec<Station> {
+        (0..app.params.limit)
+            .map(|i| station(&format!("id-{}", i), "Station", "https://s"))
+            .collect()
+    }
+
+    #[test]
+    fn go_to_page_rejects_pages_whose_offset_does_not_fit() {
+        let mut app = App::new();
+        assert!(app.go_to_page(3));
+        assert_eq!((app.page, app.params.offset), (3, 100));
+
+        assert!(!app.go_to_page(100_000_000));
+        assert!(!app.go_to_page(0));
+        assert_eq!((app.page, app.params.offset), (3, 100));
+    }
+
+    #[test]
+    fn full_page_that_is_actually_last_recovers_after_empty_next_page() {
+        let mut app = App::new();
+        app.params.limit = 10;
+        app.set_stations(full_page(&app));
+        assert!(app.has_next_page);
+
+        assert!(app.next_page());
+        assert_eq!(app.page, 2);
+        app.selected = 0;
+        app.set_stations(Vec::new());
+
+        assert_eq!(app.page, 1);
+        assert_eq!(app.params.offset, 0);
+        assert!(!app.has_next_page);
+        assert!(!app.loading);
+        assert_eq!(app.stations.len(), 10);
+        assert!(!app.next_page());
+    }
+
+    #[test]
+    fn partial_page_marks_the_end_of_results() {
+        let mut app = App::new();
+        app.params.limit = 10;
+        app.set_stations(vec![station("id-1", "One", "https://one")]);
+
+        assert!(!app.has_next_page);
+        assert_eq!(app.loaded_page, 1);
+        assert!(!app.next_page());
+        assert_eq!(app.page, 1);
+    }
+
+    #[test]
+    fn empty_first_page_shows_no_results() {
+        let mut app = App::new();
+        app.set_stations(Vec::new());
+
+        assert!(app.stations.is_empty());
+        assert!(!app.has_next_page);
+        assert_eq!(app.page, 1);
+    }
+
+    #[test]
+    fn empty_result_without_previous_stations_returns_to_first_page() {
+        let mut app = App::new();
+        app.params.limit = 50;
+        app.page = 4;
+        app.params.offset = 150;
+
+        app.set_stations(Vec::new());
+
+        assert_eq!(app.page, 1);
+        assert_eq!(app.params.offset, 0);
+        assert!(!app.has_next_page);
+        assert!(!app.prev_page());
+
+        app.set_stations(vec![station("a", "A", "http://a")]);
+        assert_eq!(app.page, 1);
+        assert_eq!(app.stations.len(), 1);
     }
 
     #[test]
@@ -475,4 +1784,965 @@ mod tests {
         assert_eq!(app.now_playing_error(), Some("playback failed"));
         assert_eq!(app.active_error(), Some("favorites failed"));
     }
+
+    #[test]
+    fn apply_session_restores_filters_and_view_mode() {
+        let mut app = App::new();
+        app.page = 4;
+        app.params.offset = 150;
+        let snapshot = SessionSnapshot::from_url(
+            "cradio://session?name=Jazz&country=de&bitrate=128&bitrate_max=320&codec=aac&view=favorites&play=id-1",
+        )
+        .expect("link parses");
+
+        app.apply_session(&snapshot);
+
+        assert_eq!(app.draft_name, "Jazz");
+        assert_eq!(app.params.name, "Jazz");
+        assert_eq!(app.params.country, "DE");
+        assert_eq!(app.params.bitrate_min, Some(128));
+        assert_eq!(app.draft_bitrate, "128");
+        assert_eq!(app.params.bitrate_max, Some(320));
+        assert_eq!(app.draft_bitrate_max, "320");
+        assert_eq!(app.params.codec, "AAC");
+        assert_eq!(app.page, 1);
+        assert_eq!(app.params.offset, 0);
+        assert_eq!(app.view_mode, StationViewMode::Favorites);
+    }
+
+    #[test]
+    fn session_snapshot_includes_playing_station() {
+        let mut app = App::new();
+        app.params.tags = "rock".to_string();
+        app.current_station = Some(station("id-9", "Nine", "https://nine"));
+
+        let snapshot = app.session_snapshot();
+
+        assert_eq!(snapshot.tags, "rock");
+        assert_eq!(snapshot.playing_uuid.as_deref(), Some("id-9"));
+        assert_eq!(snapshot.view_mode, StationViewMode::AllStations);
+    }
+
+    #[test]
+    fn reconcile_favorite_stations_keeps_selected_station() {
+        let mut app = App::new();
+        for id in ["id-0", "id-1", "id-2"] {
+            app.favorite_ids.insert(id.to_string());
+        }
+        app.set_view_mode(StationViewMode::Favorites);
+        app.set_favorite_stations(vec![
+            station("id-1", "One", "https://one"),
+            station("id-2", "Two", "https://two"),
+        ]);
+        app.favorites_loading = true;
+        app.selected = 1;
+
+        app.reconcile_favorite_stations(vec![
+            station("id-0", "Zero", "https://zero"),
+            station("id-1", "One", "https://one"),
+            station("id-2", "Two (HD)", "https://two"),
+        ]);
+
+        assert!(!app.favorites_loading);
+        assert_eq!(app.selected, 2);
+        assert_eq!(app.favorite_stations[2].name, "Two (HD)");
+    }
+
+    #[test]
+    fn toggle_reverse_restarts_paging_and_flips_title_arrow() {
+        let mut app = App::new();
+        app.page = 3;
+        app.params.offset = 100;
+        assert_eq!(app.stations_title(), " Stations ▼ - Page 3 - end reached ");
+
+        app.toggle_reverse();
+
+        assert!(!app.params.reverse);
+        assert_eq!(app.page, 1);
+        assert_eq!(app.params.offset, 0);
+        assert!(app.loading);
+        assert_eq!(app.stations_title(), " Stations ▲ - Page 1 - end reached ");
+    }
+
+    #[test]
+    fn stations_title_counts_stations_left_by_the_local_filter() {
+        let mut app = App::new();
+        app.params.limit = 50;
+        let stations: Vec<Station> = (0..12)
+            .map(|i| station(&i.to_string(), "Station", "https://s"))
+            .collect();
+
+        app.set_search_results(stations.clone(), 50);
+        assert_eq!(app.filtered_from, Some(50));
+        assert!(app.has_next_page);
+        assert_eq!(
+            app.stations_title(),
+            " Stations ▼ - Page 1 - 12 of 50 matching - more available "
+        );
+
+        app.set_stations(stations);
+        assert_eq!(app.filtered_from, None);
+        assert_eq!(app.stations_title(), " Stations ▼ - Page 1 - end reached ");
+    }
+
+    #[test]
+    fn stations_title_can_show_result_range() {
+        let mut app = App::new();
+        app.settings.show_range = true;
+        assert_eq!(
+            app.stations_title(),
+            " Stations ▼ - No results - end reached "
+        );
+
+        app.params.limit = 50;
+        app.page = 2;
+        app.params.offset = 50;
+        app.set_stations(
+            (0..50)
+                .map(|i| station(&i.to_string(), "Station", "https://s"))
+                .collect(),
+        );
+        assert_eq!(
+            app.stations_title(),
+            " Stations ▼ - Showing 51–100 - more available "
+        );
+
+        app.set_stations(vec![station("last", "Last", "https://last")]);
+        assert_eq!(
+            app.stations_title(),
+            " Stations ▼ - Showing 51–51 - end reached "
+        );
+    }
+
+    #[test]
+    fn cycle_sort_rotates_keys_and_returns_to_api_order() {
+        let mut app = App::new();
+        let mut a = station("a", "beta", "https://a");
+        a.bitrate = 128;
+        a.country_code = "DE".to_string();
+        let mut b = station("b", "", "https://b");
+        b.bitrate = 320;
+        let mut c = station("c", "Alpha", "https://c");
+        c.country_code = "AT".to_string();
+        let mut d = station("d", "alpha", "https://d");
+        d.bitrate = 128;
+        d.country_code = "DE".to_string();
+        app.set_stations(vec![a, b, c, d]);
+        app.selected = 1;
+        let order = |app: &App| -> Vec<String> {
+            app.stations
+                .iter()
+                .map(|station| station.stationuuid.clone())
+                .collect()
+        };
+
+        app.cycle_sort();
+        assert_eq!(app.sort_key, Some(SortKey::Name));
+        assert_eq!(order(&app), ["c", "d", "a", "b"]);
+        assert_eq!(app.selected, 3);
+        assert!(app.stations_title().contains(" - by name ▲"));
+
+        app.cycle_sort();
+        assert!(app.sort_desc);
+        assert_eq!(order(&app), ["b", "a", "d", "c"]);
+        assert!(app.stations_title().contains(" - by bitrate ▼"));
+
+        app.cycle_sort();
+        assert_eq!(order(&app), ["c", "a", "d", "b"]);
+
+        app.cycle_sort();
+        assert_eq!(app.sort_key, None);
+        assert_eq!(order(&app), ["a", "b", "c", "d"]);
+        assert!(!app.stations_title().contains(" - by "));
+    }
+
+    #[test]
+    fn cycle_codec_rotates_filters_and_restarts_paging() {
+        let mut app = App::new();
+        app.page = 3;
+        app.params.offset = 100;
+
+        app.cycle_codec();
+        assert_eq!(app.params.codec, "MP3");
+        assert_eq!((app.page, app.params.offset), (1, 0));
+        assert!(app.loading);
+        assert!(app.stations_title().contains(" - MP3 only"));
+
+        app.cycle_codec();
+        assert_eq!(app.params.codec, "AAC");
+        app.cycle_codec();
+        assert_eq!(app.params.codec, "OGG");
+        app.cycle_codec();
+        assert_eq!(app.params.codec, "");
+        assert!(!app.stations_title().contains(" only"));
+    }
+
+    #[test]
+    fn active_sort_applies_to_new_search_results() {
+        let mut app = App::new();
+        app.cycle_sort();
+        app.set_stations(vec![
+            station("z", "Zulu", "https://z"),
+            station("a", "Alpha", "https://a"),
+        ]);
+
+        assert_eq!(app.stations[0].stationuuid, "a");
+    }
+
+    #[test]
+    fn favorites_title_signals_background_refresh() {
+        let mut app = App::new();
+        app.set_view_mode(StationViewMode::Favorites);
+        app.favorites_loading = true;
+        assert_eq!(app.stations_title(), " Favorites - refreshing... ");
+
+        app.favorites_loading = false;
+        assert_eq!(app.stations_title(), " Favorites ");
+    }
+
+    #[test]
+    fn favorites_first_floats_favorites_and_keeps_relative_order() {
+        let mut app = App::new();
+        app.settings.favorites_first = true;
+        app.favorite_ids.insert("id-2".to_string());
+        app.favorite_ids.insert("id-4".to_string());
+
+        app.set_stations(
+            (0..5)
+                .map(|i| station(&format!("id-{}", i), &format!("S{}", i), "https://s"))
+                .collect(),
+        );
+
+        let order: Vec<&str> = app
+            .stations
+            .iter()
+            .map(|station| station.stationuuid.as_str())
+            .collect();
+        assert_eq!(order, vec!["id-2", "id-4", "id-0", "id-1", "id-3"]);
+    }
+
+    #[test]
+    fn enabling_favorites_first_keeps_the_selected_station() {
+        let mut app = App::new();
+        app.favorite_ids.insert("id-2".to_string());
+        app.set_stations(
+            (0..3)
+                .map(|i| station(&format!("id-{}", i), &format!("S{}", i), "https://s"))
+                .collect(),
+        );
+        app.selected = 1;
+
+        let mut settings = app.settings.clone();
+        settings.favorites_first = true;
+        app.apply_settings(settings).expect("valid settings");
+
+        assert_eq!(app.stations[0].stationuuid, "id-2");
+        assert_eq!(app.selected, 2);
+        assert_eq!(app.stations[app.selected].stationuuid, "id-1");
+    }
+
+    #[test]
+    fn refresh_favorites_always_switches_view_and_requests_every_favorite() {
+        let mut app = App::new();
+        assert_eq!(app.refresh_favorites(), None);
+        assert_eq!(app.view_mode, StationViewMode::Favorites);
+        assert!(!app.favorites_loading);
+
+        app.set_stations(vec![
+            station("id-2", "beta", "https://two"),
+            station("id-1", "Alpha", "https://one"),
+        ]);
+        app.set_view_mode(StationViewMode::AllStations);
+        app.toggle_favorite_at(0);
+        app.toggle_favorite_at(1);
+        app.set_view_mode(StationViewMode::Favorites);
+        app.favorites_error = Some("stale".to_string());
+
+        assert_eq!(
+            app.refresh_favorites(),
+            Some(vec!["id-2".to_string(), "id-1".to_string()])
+        );
+        assert!(app.favorites_loading);
+        assert!(app.favorites_error.is_none());
+        assert_eq!(app.favorite_stations[0].name, "Alpha");
+    }
+
+    #[test]
+    fn show_cached_favorites_does_not_start_a_refresh() {
+        let mut app = App::new();
+        app.set_stations(vec![station("id-1", "One", "https://one")]);
+        app.toggle_favorite_at(0);
+
+        app.show_cached_favorites();
+
+        assert_eq!(app.view_mode, StationViewMode::Favorites);
+        assert!(!app.favorites_loading);
+        assert_eq!(app.favorite_stations.len(), 1);
+    }
+
+    #[test]
+    fn remap_favorite_uuids_follows_merged_stations() {
+        let mut app = App::new();
+        app.set_favorites(
+            ["old-1", "old-2", "id-3"]
+                .into_iter()
+                .map(|id| FavoriteEntry {
+                    stationuuid: id.to_string(),
+                    name: id.to_string(),
+                    url: "https://s".to_string(),
+                    url_override: None,
+                })
+                .collect(),
+        );
+
+        let remapped = app.remap_favorite_uuids(&[
+            ("old-1".to_string(), station("new-1", "One", "https://one")),
+            (
+                "old-2".to_string(),
+                station("id-3", "Three", "https://three"),
+            ),
+            (
+                "id-3".to_string(),
+                station("id-3", "Three", "https://three"),
+            ),
+        ]);
+
+        assert_eq!(remapped, 2);
+        let uuids: Vec<&str> = app
+            .favorites
+            .iter()
+            .map(|fav| fav.stationuuid.as_str())
+            .collect();
+        assert_eq!(uuids, vec!["new-1", "id-3"]);
+        assert!(app.is_favorite("new-1"));
+        assert!(!app.is_favorite("old-1"));
+        assert!(!app.is_favorite("old-2"));
+    }
+
+    #[test]
+    fn reconcile_favorite_stations_drops_stations_removed_during_refresh() {
+        let mut app = App::new();
+        app.favorite_ids.insert("id-1".to_string());
+        app.set_view_mode(StationViewMode::Favorites);
+
+        app.reconcile_favorite_stations(vec![
+            station("id-1", "One", "https://one"),
+            station("id-2", "Two", "https://two"),
+        ]);
+
+        assert_eq!(app.favorite_stations.len(), 1);
+        assert_eq!(app.favorite_stations[0].stationuuid, "id-1");
+    }
+
+    #[test]
+    fn toggle_favorite_at_uses_visible_row_offset() {
+        let mut app = App::new();
+        app.stations = (0..12)
+            .map(|i| station(&format!("id-{}", i), &format!("S{}", i), "https://s"))
+            .collect();
+        app.scroll_offset = 5;
+        app.selected = 6;
+
+        let index = app.visible_row_index(5, 2).expect("row is visible");
+        assert_eq!(index, 7);
+        assert_eq!(app.toggle_favorite_at(index), Some(true));
+        assert!(app.is_favorite("id-7"));
+        assert_eq!(app.selected, 6);
+        assert_eq!(app.visible_row_index(5, 7), None);
+    }
+
+    #[test]
+    fn toggle_favorite_at_in_favorites_view_keeps_selected_station() {
+        let mut app = App::new();
+        app.stations = vec![
+            station("id-1", "One", "https://one"),
+            station("id-2", "Two", "https://two"),
+            station("id-3", "Three", "https://three"),
+        ];
+        for index in 0..3 {
+            app.toggle_favorite_at(index);
+        }
+        app.favorite_stations = app.stations.clone();
+        app.set_view_mode(StationViewMode::Favorites);
+        app.selected = 2;
+
+        assert_eq!(app.toggle_favorite_at(0), Some(false));
+        assert_eq!(app.favorite_stations.len(), 2);
+        assert_eq!(app.selected, 1);
+        assert_eq!(
+            app.selected_station().map(|s| s.stationuuid.as_str()),
+            Some("id-3")
+        );
+    }
+
+    #[test]
+    fn apply_settings_falls_back_to_default_keys_on_conflicts() {
+        let mut app = App::new();
+        let mut settings = Settings::default();
+        settings.keys.insert("stop".to_string(), "k".to_string());
+        app.apply_settings(settings.clone()).expect("valid keys");
+        assert_eq!(app.keymap.label(Action::Stop), "k");
+
+        settings.keys.insert("share".to_string(), "k".to_string());
+        let err = app.apply_settings(settings).expect_err("conflict");
+
+        assert_eq!(
+            err,
+            "Conflicting key bindings: 'k' (stop, share). Using default keys."
+        );
+        assert_eq!(app.keymap, KeyMap::default());
+        assert_eq!(app.settings.keys.len(), 2);
+    }
+
+    #[test]
+    fn layout_preferences_are_restored_from_settings() {
+        let mut app = App::new();
+        app.toggle_mini();
+        app.set_theme(Theme::Mono);
+        assert!(app.mini && app.monochrome);
+
+        let mut restored = App::new();
+        restored
+            .apply_settings(app.settings.clone())
+            .expect("valid settings");
+        assert!(restored.mini);
+        assert!(restored.monochrome);
+
+        restored.set_theme(Theme::Default);
+        assert!(!restored.monochrome);
+        restored.no_color = true;
+        restored.set_theme(Theme::Default);
+        assert!(restored.monochrome);
+    }
+
+    #[test]
+    fn saved_searches_replace_the_drafts_and_params() {
+        let mut app = App::new();
+        app.draft_name = "old".to_string();
+        app.page = 4;
+        app.settings.saved_searches.insert(
+            "jazz".to_string(),
+            SavedSearch {
+                tags: "jazz".to_string(),
+                country: "us".to_string(),
+                bitrate: Some(128),
+                codec: "mp3".to_string(),
+                ..SavedSearch::default()
+            },
+        );
+        app.settings
+            .saved_searches
+            .insert("any".to_string(), SavedSearch::default());
+
+        assert_eq!(app.apply_saved_search("jazz"), Ok(()));
+        assert_eq!(app.draft_name, "");
+        assert_eq!(app.draft_bitrate, "128");
+        assert_eq!(app.params.tags, "jazz");
+        assert_eq!(app.params.country, "US");
+        assert_eq!(app.params.bitrate_min, Some(128));
+        assert_eq!(app.params.codec, "MP3");
+        assert_eq!(app.page, 1);
+
+        assert_eq!(
+            app.apply_saved_search("rock"),
+            Err("Unknown saved search: rock".to_string())
+        );
+        assert_eq!(app.params.tags, "jazz");
+
+        assert_eq!(app.apply_saved_search("any"), Ok(()));
+        assert_eq!(app.params.codec, "");
+    }
+
+    #[test]
+    fn default_filters_prefill_drafts_and_params() {
+        let mut app = App::new();
+        app.settings.default_country = Some("de".to_string());
+        app.settings.default_language = Some("German".to_string());
+
+        app.apply_default_filters();
+
+        assert_eq!(app.draft_country, "de");
+        assert_eq!(app.draft_language, "German");
+        assert_eq!(app.params.country, "DE");
+        assert_eq!(app.params.language, "german");
+        assert_eq!(app.draft_name, "");
+    }
+
+    #[test]
+    fn dropped_stream_is_remembered_for_reconnect() {
+        let mut app = App::new();
+        assert!(app.reconnect_station().is_none());
+
+        app.current_station = Some(station("id-1", "One", "https://one"));
+        assert_eq!(
+            app.reconnect_station().map(|s| s.stationuuid),
+            Some("id-1".to_string())
+        );
+
+        app.note_stream_dropped();
+        assert!(app.current_station.is_none());
+        assert_eq!(
+            app.playback_error.as_deref(),
+            Some("One dropped. Press R to reconnect")
+        );
+        assert_eq!(
+            app.reconnect_station().map(|s| s.stationuuid),
+            Some("id-1".to_string())
+        );
+
+        app.forget_current_station();
+        assert!(app.reconnect_station().is_none());
+
+        app.current_station = Some(station("id-2", "Two", "https://two"));
+        app.note_stream_unavailable();
+        assert!(app.current_station.is_none());
+        assert_eq!(
+            app.playback_error.as_deref(),
+            Some("Stream unavailable: Two. Press R to retry")
+        );
+        assert_eq!(
+            app.reconnect_station().map(|s| s.stationuuid),
+            Some("id-2".to_string())
+        );
+    }
+
+    #[test]
+    fn forget_current_station_clears_ui_playback_state() {
+        let mut app = App::new();
+        app.current_station = Some(station("id-1", "One", "https://one"));
+        app.playback_error = Some("stale".to_string());
+
+        let forgotten = app.forget_current_station();
+
+        assert_eq!(forgotten.map(|s| s.stationuuid), Some("id-1".to_string()));
+        assert!(app.current_station.is_none());
+        assert!(app.playback_error.is_none());
+        assert!(app.forget_current_station().is_none());
+    }
+
+    #[test]
+    fn favorite_limit_asks_for_confirmation_before_exceeding_cap() {
+        let mut app = App::new();
+        app.settings.max_favorites = Some(1);
+        app.stations = vec![
+            station("id-1", "One", "https://one"),
+            station("id-2", "Two", "https://two"),
+        ];
+        assert_eq!(app.check_favorite_limit(0), Ok(()));
+        app.toggle_favorite_at(0);
+
+        let warning = app.check_favorite_limit(1).expect_err("limit reached");
+        assert!(warning.contains("Favorites limit (1) reached"));
+        assert_eq!(app.check_favorite_limit(0), Ok(()));
+        assert_eq!(app.check_favorite_limit(1), Ok(()));
+        app.toggle_favorite_at(1);
+        assert_eq!(app.favorites.len(), 2);
+
+        assert!(
+            app.favorites_limit_warning()
+                .expect("over the limit")
+                .starts_with("2 favorites stored")
+        );
+        app.settings.max_favorites = None;
+        assert_eq!(app.favorites_limit_warning(), None);
+    }
+
+    #[test]
+    fn imports_are_capped_at_the_favorites_limit() {
+        let entry = |id: &str| FavoriteEntry {
+            stationuuid: id.to_string(),
+            name: id.to_string(),
+            url: format!("https://{}", id),
+            url_override: None,
+        };
+        let mut app = App::new();
+        let mut merged = vec![entry("url:1"), entry("url:2"), entry("url:3")];
+        assert_eq!(app.cap_imported_favorites(&mut merged), 0);
+        assert_eq!(merged.len(), 3);
+
+        app.settings.max_favorites = Some(1);
+        assert_eq!(app.cap_imported_favorites(&mut merged), 2);
+        assert_eq!(merged, vec![entry("url:1")]);
+
+        app.set_favorites(vec![entry("id-1"), entry("id-2")]);
+        let mut merged = vec![entry("id-1"), entry("id-2"), entry("url:1")];
+        assert_eq!(app.cap_imported_favorites(&mut merged), 1);
+        assert_eq!(merged, vec![entry("id-1"), entry("id-2")]);
+        assert!(app.favorites_limit_warning().is_some());
+    }
+
+    #[test]
+    fn url_override_only_applies_to_favorites() {
+        let mut app = App::new();
+        app.set_stations(vec![
+            station("id-1", "One", "https://one"),
+            station("id-2", "Two", "https://two"),
+        ]);
+        app.toggle_favorite_at(0);
+
+        assert_eq!(
+            app.set_url_override_for_selected(Some("https://one/direct".to_string())),
+            Ok("One".to_string())
+        );
+        assert_eq!(app.url_override("id-1"), Some("https://one/direct"));
+
+        app.selected = 1;
+        assert!(
+            app.set_url_override_for_selected(Some("https://two/direct".to_string()))
+                .is_err()
+        );
+        assert_eq!(app.url_override("id-2"), None);
+
+        assert_eq!(app.playback_url(&app.stations[0]), "https://one/direct");
+
+        app.selected = 0;
+        app.set_url_override_for_selected(None).expect("clear");
+        assert_eq!(app.url_override("id-1"), None);
+        assert_eq!(app.playback_url(&app.stations[0]), "https://one");
+    }
+
+    #[test]
+    fn playback_url_follows_the_resolved_url_preference() {
+        let mut app = App::new();
+        let mut resolved = station("id-1", "One", "https://one/playlist.pls");
+        resolved.url_resolved = "https://one/live".to_string();
+        let unresolved = station("id-2", "Two", "https://two");
+
+        assert_eq!(app.playback_url(&resolved), "https://one/live");
+        assert!(!app.toggle_prefer_resolved());
+        assert_eq!(app.playback_url(&resolved), "https://one/playlist.pls");
+        assert_eq!(app.playback_url(&unresolved), "https://two");
+        assert!(app.toggle_prefer_resolved());
+        assert_eq!(app.playback_url(&unresolved), "https://two");
+    }
+
+    #[test]
+    fn find_similar_searches_by_the_favorites_first_tag_and_country() {
+        let mut app = App::new();
+        let mut jazz = station("id-1", "Jazz FM", "https://jazz");
+        jazz.tags = " jazz , smooth jazz".to_string();
+        jazz.country_code = "DE".to_string();
+        app.favorite_stations = vec![jazz, station("id-2", "Cached", "https://cached")];
+        app.set_view_mode(StationViewMode::Favorites);
+        app.draft_name = "old query".to_string();
+
+        assert_eq!(app.find_similar_to_selected(), Ok("Jazz FM".to_string()));
+        assert_eq!(app.view_mode, StationViewMode::AllStations);
+        assert!(app.loading);
+        assert_eq!(app.params.tags, "jazz");
+        assert_eq!(app.params.country, "DE");
+        assert!(app.params.name.is_empty());
+
+        app.set_view_mode(StationViewMode::Favorites);
+        app.selected = 1;
+        assert_eq!(
+            app.find_similar_to_selected(),
+            Err(
+                "No tags or country known for Cached. Press l to refresh favorites first"
+                    .to_string()
+            )
+        );
+        assert_eq!(app.view_mode, StationViewMode::Favorites);
+    }
+
+    #[test]
+    fn early_failure_tries_the_other_url_once_and_remembers_what_worked() {
+        let mut app = App::new();
+        let mut playlist = station("id-1", "One", "https://one/direct");
+        playlist.url_resolved = "https://one/playlist.m3u".to_string();
+        let start = Instant::now();
+        app.current_station = Some(playlist.clone());
+        app.playing_url = Some(app.playback_url(&playlist));
+        app.playing_since = Some(start);
+
+        let (_, alternate) = app
+            .alternate_after_early_failure(start + Duration::from_secs(1))
+            .expect("alternate url");
+        assert_eq!(alternate, "https://one/direct");
+        assert!(
+            app.alternate_after_early_failure(start + Duration::from_secs(30))
+                .is_none()
+        );
+
+        app.playing_url = Some(alternate);
+        app.retried_alternate = true;
+        assert!(app.alternate_after_early_failure(start).is_none());
+
+        app.remember_working_url(start + Duration::from_secs(1));
+        assert!(app.working_urls.is_empty());
+        app.remember_working_url(start + Duration::from_secs(6));
+        assert_eq!(app.playback_url(&playlist), "https://one/direct");
+
+        app.toggle_prefer_resolved();
+        assert!(app.working_urls.is_empty());
+    }
+
+    #[test]
+    fn navigation_repeats_are_throttled_to_the_configured_rate() {
+        let mut app = App::new();
+        let start = Instant::now();
+        assert!(app.allow_navigation(KeyCode::Down, start));
+        assert!(app.allow_navigation(KeyCode::Down, start));
+
+        app.settings.nav_repeat_ms = 50;
+        let start = start + Duration::from_secs(1);
+        assert!(app.allow_navigation(KeyCode::Down, start));
+        assert!(!app.allow_navigation(KeyCode::Down, start + Duration::from_millis(20)));
+        assert!(!app.allow_navigation(KeyCode::Down, start + Duration::from_millis(49)));
+        assert!(app.allow_navigation(KeyCode::Down, start + Duration::from_millis(50)));
+        assert!(app.allow_navigation(KeyCode::Up, start + Duration::from_millis(60)));
+        assert!(app.allow_navigation(KeyCode::Enter, start + Duration::from_millis(60)));
+        assert!(!app.allow_navigation(KeyCode::Up, start + Duration::from_millis(70)));
+    }
+
+    #[test]
+    fn quitting_asks_for_confirmation_only_when_configured() {
+        let mut app = App::new();
+        assert!(app.request_quit());
+
+        app.settings.confirm_quit = ConfirmQuit::Playing;
+        assert!(app.request_quit());
+        assert_eq!(app.mode, AppMode::Normal);
+
+        app.current_station = Some(station("id-1", "One", "https://one"));
+        assert!(!app.request_quit());
+        assert_eq!(app.mode, AppMode::ConfirmQuit);
+
+        app.mode = AppMode::Normal;
+        app.current_station = None;
+        app.settings.confirm_quit = ConfirmQuit::Always;
+        assert!(!app.request_quit());
+        assert_eq!(app.mode, AppMode::ConfirmQuit);
+    }
+
+    #[test]
+    fn now_playing_text_prefers_the_track_over_the_station_name() {
+        let mut app = App::new();
+        assert_eq!(app.now_playing_text(), None);
+
+        app.current_station = Some(station("id-1", "Jazz FM", "https://jazz"));
+        assert_eq!(app.now_playing_text(), Some("Jazz FM".to_string()));
+
+        app.current_track = Some("  ".to_string());
+        assert_eq!(app.now_playing_text(), Some("Jazz FM".to_string()));
+
+        app.current_track = Some("Miles Davis - So What".to_string());
+        assert_eq!(
+            app.now_playing_text(),
+            Some("Miles Davis - So What".to_string())
+        );
+
+        app.forget_current_station();
+        assert_eq!(app.current_track, None);
+        assert_eq!(app.now_playing_text(), None);
+    }
+
+    #[test]
+    fn window_title_shows_station_bitrate_and_elapsed_time() {
+        let mut app = App::new();
+        let start = Instant::now();
+        assert_eq!(app.window_title(start), "cradio");
+
+        let mut playing = station("id-1", "Jazz FM", "https://jazz");
+        playing.bitrate = 128;
+        app.current_station = Some(playing);
+        app.playing_since = Some(start);
+        assert_eq!(
+            app.window_title(start + Duration::from_secs(185)),
+            "Jazz FM · 128 kbps · 3:05 - cradio"
+        );
+        assert_eq!(
+            app.window_title(start + Duration::from_secs(3725)),
+            "Jazz FM · 128 kbps · 1:02:05 - cradio"
+        );
+
+        app.current_station.as_mut().expect("playing").bitrate = 0;
+        app.playing_since = None;
+        assert_eq!(app.window_title(start), "Jazz FM - cradio");
+    }
+
+    #[test]
+    fn filter_input_stops_at_the_maximum_length() {
+        let mut app = App::new();
+        app.mode = AppMode::Filtering(InputField::Name);
+        for _ in 0..MAX_FILTER_LEN {
+            assert!(app.push_filter_char('é'));
+        }
+        assert!(!app.push_filter_char('x'));
+        assert_eq!(app.draft_name.chars().count(), MAX_FILTER_LEN);
+
+        app.mode = AppMode::Filtering(InputField::Bitrate);
+        assert!(app.push_filter_char('k'));
+        assert!(app.push_filter_char('9'));
+        assert_eq!(app.draft_bitrate, "9");
+    }
+
+    #[test]
+    fn bitrate_range_comes_from_the_min_and_max_fields() {
+        let mut app = App::new();
+        app.mode = AppMode::Filtering(InputField::Bitrate);
+        app.next_field();
+        assert_eq!(app.mode, AppMode::Filtering(InputField::BitrateMax));
+        assert!(app.push_filter_char('x'));
+        assert!(app.push_filter_char('1'));
+        assert!(app.push_filter_char('2'));
+        assert!(app.push_filter_char('8'));
+        app.next_field();
+        assert_eq!(app.mode, AppMode::Filtering(InputField::Name));
+
+        app.draft_bitrate = "64".to_string();
+        app.update_params_from_drafts();
+        assert_eq!(app.params.bitrate_min, Some(64));
+        assert_eq!(app.params.bitrate_max, Some(128));
+
+        app.draft_bitrate = "320".to_string();
+        app.update_params_from_drafts();
+        assert_eq!(app.params.bitrate_min, Some(128));
+        assert_eq!(app.params.bitrate_max, Some(320));
+
+        app.draft_bitrate_max = "lots".to_string();
+        app.update_params_from_drafts();
+        assert_eq!(app.params.bitrate_max, None);
+    }
+
+    #[test]
+    fn apply_favorite_updates_rewrites_stored_entries() {
+        let mut app = App::new();
+        app.set_favorites(vec![
+            FavoriteEntry {
+                stationuuid: "id-1".to_string(),
+                name: "One".to_string(),
+                url: "https://one".to_string(),
+                url_override: None,
+            },
+            FavoriteEntry {
+                stationuuid: "id-2".to_string(),
+                name: "Two".to_string(),
+                url: "https://two".to_string(),
+                url_override: None,
+            },
+        ]);
+        app.pending_favorite_updates = vec![
+            FavoriteEntry {
+                stationuuid: "id-2".to_string(),
+                name: "Two FM".to_string(),
+                url: "https://two-fm".to_string(),
+                url_override: None,
+            },
+            FavoriteEntry {
+                stationuuid: "gone".to_string(),
+                name: "Gone".to_string(),
+                url: "https://gone".to_string(),
+                url_override: None,
+            },
+        ];
+
+        assert_eq!(app.apply_favorite_updates(), 1);
+        assert!(app.pending_favorite_updates.is_empty());
+        assert_eq!(app.favorites[0].name, "One");
+        assert_eq!(app.favorites[1].name, "Two FM");
+        assert_eq!(app.favorites[1].url, "https://two-fm");
+    }
+
+    #[test]
+    fn request_counter_never_goes_negative() {
+        let mut app = App::new();
+        app.begin_request();
+        app.begin_request();
+        app.finish_request();
+        assert_eq!(app.requests_in_flight, 1);
+
+        app.finish_request();
+        app.finish_request();
+        assert_eq!(app.requests_in_flight, 0);
+    }
+
+    #[tokio::test]
+    async fn abort_tasks_cancels_outstanding_loads() {
+        let mut app = App::new();
+        app.track_task(tokio::spawn(std::future::pending::<()>()));
+        let finished = tokio::spawn(async {});
+        while !finished.is_finished() {
+            tokio::task::yield_now().await;
+        }
+        app.track_task(finished);
+        app.track_task(tokio::spawn(std::future::pending::<()>()));
+        assert_eq!(app.tasks.len(), 2);
+
+        let pending: Vec<_> = app.tasks.iter().map(|task| task.abort_handle()).collect();
+        app.abort_tasks();
+        assert!(app.tasks.is_empty());
+        for task in pending {
+            while !task.is_finished() {
+                tokio::task::yield_now().await;
+            }
+        }
+    }
+
+    #[test]
+    fn quick_filter_narrows_loaded_stations_without_losing_the_selection() {
+        let mut app = App::new();
+        app.stations = vec![
+            station("a", "Jazz FM", "https://a"),
+            station("b", "Rock Antenne", "https://b"),
+            station("c", "Smooth JAZZ", "https://c"),
+        ];
+        app.selected = 1;
+        app.start_quick_filter();
+        assert_eq!(app.quick_filtered_indices(), vec![0, 1, 2]);
+
+        for c in "jaz".chars() {
+            app.push_quick_filter_char(c);
+        }
+        assert_eq!(app.mode, AppMode::QuickFilter("jaz".to_string()));
+        assert_eq!(app.quick_filtered_indices(), vec![0, 2]);
+        assert_eq!(app.selected, 0);
+
+        app.select_next(0);
+        assert_eq!(app.selected, 2);
+        app.select_next(0);
+        assert_eq!(app.selected, 2);
+        app.select_prev();
+        assert_eq!(app.selected, 0);
+
+        app.push_quick_filter_char('q');
+        assert!(app.quick_filtered_indices().is_empty());
+        app.pop_quick_filter_char();
+        assert_eq!(app.quick_filtered_indices(), vec![0, 2]);
+
+        app.cancel_quick_filter();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.selected, 1);
+        assert_eq!(app.quick_filtered_indices(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn accepting_the_quick_filter_keeps_the_matched_station_selected() {
+        let mut app = App::new();
+        app.stations = vec![
+            station("a", "Jazz FM", "https://a"),
+            station("b", "Rock Antenne", "https://b"),
+        ];
+        app.start_quick_filter();
+        app.push_quick_filter_char('r');
+        app.push_quick_filter_char('o');
+        assert!(app.accept_quick_filter());
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.selected_station().map(|s| s.name.as_str()),
+            Some("Rock Antenne")
+        );
+
+        app.start_quick_filter();
+        app.push_quick_filter_char('x');
+        assert!(!app.accept_quick_filter());
+    }
+
+    #[test]
+    fn quick_filter_matches_ignore_case_on_char_boundaries() {
+        assert_eq!(quick_filter_match("Smooth JAZZ", "jazz"), Some(7..11));
+        assert_eq!(quick_filter_match("Radio Ö1", "ö1"), Some(6..9));
+        assert_eq!(quick_filter_match("Radio", ""), Some(0..0));
+        assert_eq!(quick_filter_match("Radio", "radios"), None);
+    }
 }
@@ -0,0 +1,48 @@
+use std::process::Command;
+
+fn parse_pactl_sinks(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split('\t').nth(1))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn list_output_devices() -> Result<Vec<String>, String> {
+    let output = Command::new("pactl")
+        .args(["list", "short", "sinks"])
+        .output()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                "pactl not found. Set audio_device in settings.json instead".to_string()
+            } else {
+                format!("Failed to list audio devices: {}", e)
+            }
+        })?;
+    if !output.status.success() {
+        return Err(format!("Failed to list audio devices: {}", output.status));
+    }
+    Ok(parse_pactl_sinks(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_pactl_sinks;
+
+    #[test]
+    fn parses_sink_names_from_pactl_short_output() {
+        let output = "0\talsa_output.pci-0000_00_1f.3.analog-stereo\tPipeWire\ts32le 2ch 48000Hz\tRUNNING\n\
+                      1\tbluez_output.00_11_22_33_44_55.1\tPipeWire\ts16le 2ch 48000Hz\tSUSPENDED\n\
+                      \n";
+
+        assert_eq!(
+            parse_pactl_sinks(output),
+            vec![
+                "alsa_output.pci-0000_00_1f.3.analog-stereo".to_string(),
+                "bluez_output.00_11_22_33_44_55.1".to_string(),
+            ]
+        );
+    }
+}
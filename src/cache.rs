@@ -0,0 +1,120 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{api::Station, json_file, paths};
+
+const MAX_AGE_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchCache {
+    pub saved_at: u64,
+    pub stations: Vec<Station>,
+}
+
+impl SearchCache {
+    pub fn age_label(&self) -> String {
+        describe_age(now_secs().saturating_sub(self.saved_at))
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+fn cache_path() -> Result<PathBuf, String> {
+    Ok(paths::cache_dir()?.join("cache.json"))
+}
+
+fn load_cache_from_path(path: &Path, now: u64) -> Option<SearchCache> {
+    let cache: SearchCache = json_file::load(path, "cache").ok().flatten()?;
+    (now.saturating_sub(cache.saved_at) <= MAX_AGE_SECS && !cache.stations.is_empty())
+        .then_some(cache)
+}
+
+fn describe_age(seconds: u64) -> String {
+    match seconds {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{} min ago", seconds / 60),
+        _ => format!("{} h ago", seconds / 3600),
+    }
+}
+
+pub fn load_cache() -> Option<SearchCache> {
+    load_cache_from_path(&cache_path().ok()?, now_secs())
+}
+
+pub fn save_cache(stations: &[Station]) -> Result<(), String> {
+    let cache = SearchCache {
+        saved_at: now_secs(),
+        stations: stations.to_vec(),
+    };
+    json_file::save(&cache_path()?, &cache, "cache")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchCache, describe_age, load_cache_from_path};
+    use crate::{api::Station, json_file};
+    use std::{fs, path::PathBuf};
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("cradio-cache-test-{}-{}", name, std::process::id()))
+            .join("cache.json")
+    }
+
+    fn station(id: &str) -> Station {
+        serde_json::from_str(&format!(
+            r#"{{"stationuuid":"{}","name":"Station {}","url":"https://{}","countrycode":"DE","bitrate":128}}"#,
+            id, id, id
+        ))
+        .expect("station json")
+    }
+
+    #[test]
+    fn cached_stations_roundtrip_until_they_expire() {
+        let path = temp_path("roundtrip");
+        let cache = SearchCache {
+            saved_at: 1_000,
+            stations: vec![station("a"), station("b")],
+        };
+        json_file::save(&path, &cache, "cache").expect("save should work");
+
+        let loaded = load_cache_from_path(&path, 1_000 + 3_600).expect("fresh cache");
+        assert_eq!(loaded.saved_at, 1_000);
+        assert_eq!(loaded.stations.len(), 2);
+        assert_eq!(loaded.stations[1].country_code, "DE");
+        assert_eq!(loaded.stations[1].bitrate, 128);
+
+        assert!(load_cache_from_path(&path, 1_000 + 25 * 3_600).is_none());
+        let _ = fs::remove_dir_all(path.parent().expect("parent"));
+    }
+
+    #[test]
+    fn missing_empty_or_corrupt_caches_are_ignored() {
+        let path = temp_path("corrupt");
+        assert!(load_cache_from_path(&path, 0).is_none());
+
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        fs::write(&path, "{\"saved_at\":").expect("write cache");
+        assert!(load_cache_from_path(&path, 0).is_none());
+
+        fs::write(&path, "{\"saved_at\":0,\"stations\":[]}").expect("write cache");
+        assert!(load_cache_from_path(&path, 0).is_none());
+        let _ = fs::remove_dir_all(path.parent().expect("parent"));
+    }
+
+    #[test]
+    fn cache_age_is_described_in_words() {
+        assert_eq!(describe_age(5), "just now");
+        assert_eq!(describe_age(125), "2 min ago");
+        assert_eq!(describe_age(7_300), "2 h ago");
+    }
+}
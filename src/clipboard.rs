@@ -0,0 +1,25 @@
+use std::io::{self, Write};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", STANDARD.encode(text))
+}
+
+pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    let mut stdout = io::stdout();
+    stdout
+        .write_all(osc52_sequence(text).as_bytes())
+        .and_then(|_| stdout.flush())
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::osc52_sequence;
+
+    #[test]
+    fn osc52_sequence_base64_encodes_payload() {
+        assert_eq!(osc52_sequence("cradio"), "\x1b]52;c;Y3JhZGlv\x07");
+    }
+}
@@ -0,0 +1,161 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Play(String),
+    Search(String),
+    Saved(String),
+    Goto(u32),
+    Theme(String),
+    Export {
+        format: String,
+        path: Option<String>,
+    },
+    Import(String),
+    Url(Option<String>),
+    Device(Option<String>),
+    Open(String),
+}
+
+pub fn parse_command(line: &str) -> Result<Command, String> {
+    let line = line.trim().trim_start_matches(':').trim();
+    let (name, rest) = line
+        .split_once(char::is_whitespace)
+        .map_or((line, ""), |(name, rest)| (name, rest.trim()));
+
+    match name {
+        "" => Err("Empty command".to_string()),
+        "play" if rest.is_empty() => Err("Usage: :play <uuid>".to_string()),
+        "play" => Ok(Command::Play(rest.to_string())),
+        "search" => Ok(Command::Search(rest.to_string())),
+        "saved" if rest.is_empty() => Err("Usage: :saved <name>".to_string()),
+        "saved" => Ok(Command::Saved(rest.to_string())),
+        "goto" => rest
+            .parse::<u32>()
+            .ok()
+            .filter(|page| *page > 0)
+            .map(Command::Goto)
+            .ok_or_else(|| "Usage: :goto <page>".to_string()),
+        "theme" if rest.is_empty() => Err("Usage: :theme <name>".to_string()),
+        "theme" => Ok(Command::Theme(rest.to_lowercase())),
+        "export" => {
+            let mut parts = rest.splitn(2, char::is_whitespace);
+            let format = parts.next().unwrap_or_default().to_lowercase();
+            if format.is_empty() {
+                return Err("Usage: :export <format> [path]".to_string());
+            }
+            let path = parts
+                .next()
+                .map(str::trim)
+                .filter(|path| !path.is_empty())
+                .map(str::to_string);
+            Ok(Command::Export { format, path })
+        }
+        "import" if rest.is_empty() => {
+            Err("Usage: :import <playlist.m3u|playlist.pls>".to_string())
+        }
+        "import" => Ok(Command::Import(rest.to_string())),
+        "open" if rest.is_empty() => Err("Usage: :open <stream url>".to_string()),
+        "open" => Ok(Command::Open(rest.to_string())),
+        "device" if rest.is_empty() => Ok(Command::Device(None)),
+        "device" => Ok(Command::Device(Some(rest.to_string()))),
+        "url" if rest.is_empty() => Ok(Command::Url(None)),
+        "url" => Ok(Command::Url(Some(rest.to_string()))),
+        other => Err(format!("Unknown command: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Command, parse_command};
+
+    #[test]
+    fn parses_commands_with_arguments() {
+        assert_eq!(
+            parse_command(":play abc-123"),
+            Ok(Command::Play("abc-123".to_string()))
+        );
+        assert_eq!(
+            parse_command("search  lo-fi beats "),
+            Ok(Command::Search("lo-fi beats".to_string()))
+        );
+        assert_eq!(parse_command(":goto 3"), Ok(Command::Goto(3)));
+        assert_eq!(
+            parse_command(":saved late night"),
+            Ok(Command::Saved("late night".to_string()))
+        );
+        assert_eq!(
+            parse_command(":theme Light"),
+            Ok(Command::Theme("light".to_string()))
+        );
+        assert_eq!(
+            parse_command(":export m3u"),
+            Ok(Command::Export {
+                format: "m3u".to_string(),
+                path: None
+            })
+        );
+        assert_eq!(
+            parse_command(":export M3U /tmp/my list.m3u"),
+            Ok(Command::Export {
+                format: "m3u".to_string(),
+                path: Some("/tmp/my list.m3u".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn parses_import_with_a_path() {
+        assert_eq!(
+            parse_command(":import ~/My Radio.pls"),
+            Ok(Command::Import("~/My Radio.pls".to_string()))
+        );
+        assert!(parse_command(":import").is_err());
+    }
+
+    #[test]
+    fn url_without_argument_clears_the_override() {
+        assert_eq!(
+            parse_command(":url https://direct.example/stream"),
+            Ok(Command::Url(Some(
+                "https://direct.example/stream".to_string()
+            )))
+        );
+        assert_eq!(parse_command(":url"), Ok(Command::Url(None)));
+    }
+
+    #[test]
+    fn open_takes_a_stream_url() {
+        assert_eq!(
+            parse_command(":open https://radio.example/live"),
+            Ok(Command::Open("https://radio.example/live".to_string()))
+        );
+    }
+
+    #[test]
+    fn device_without_argument_lists_devices() {
+        assert_eq!(parse_command(":device"), Ok(Command::Device(None)));
+        assert_eq!(
+            parse_command(":device bluez_output.1"),
+            Ok(Command::Device(Some("bluez_output.1".to_string())))
+        );
+    }
+
+    #[test]
+    fn search_without_query_clears_the_name_filter() {
+        assert_eq!(parse_command(":search"), Ok(Command::Search(String::new())));
+    }
+
+    #[test]
+    fn rejects_unknown_or_incomplete_commands() {
+        assert!(parse_command("").is_err());
+        assert!(parse_command(":play").is_err());
+        assert!(parse_command(":goto zero").is_err());
+        assert!(parse_command(":goto 0").is_err());
+        assert!(parse_command(":export").is_err());
+        assert!(parse_command(":open").is_err());
+        assert!(parse_command(":saved").is_err());
+        assert_eq!(
+            parse_command(":launch rockets"),
+            Err("Unknown command: launch".to_string())
+        );
+    }
+}
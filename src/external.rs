@@ -0,0 +1,80 @@
+use std::process::{Command, Stdio};
+
+fn default_opener() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    }
+}
+
+fn opener_command(custom: Option<&str>, url: &str) -> (String, Vec<String>) {
+    let mut parts = custom
+        .map(str::split_whitespace)
+        .into_iter()
+        .flatten()
+        .map(str::to_string);
+    let program = parts.next().unwrap_or_else(|| default_opener().to_string());
+    let mut args: Vec<String> = parts.collect();
+    args.push(url.to_string());
+    (program, args)
+}
+
+pub fn open_in_external_player(url: &str, custom: Option<&str>) -> Result<(), String> {
+    let (program, args) = opener_command(custom, url);
+    Command::new(&program)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                format!("No external player available: {} not found", program)
+            } else {
+                format!("Failed to launch {}: {}", program, e)
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{default_opener, open_in_external_player, opener_command};
+
+    #[test]
+    fn opener_defaults_to_the_system_handler() {
+        assert_eq!(
+            opener_command(None, "https://a/stream"),
+            (
+                default_opener().to_string(),
+                vec!["https://a/stream".to_string()]
+            )
+        );
+        assert_eq!(
+            opener_command(Some("   "), "https://a/stream").0,
+            default_opener()
+        );
+    }
+
+    #[test]
+    fn custom_player_arguments_come_before_the_url() {
+        assert_eq!(
+            opener_command(Some("mpv --force-window"), "https://a/stream"),
+            (
+                "mpv".to_string(),
+                vec!["--force-window".to_string(), "https://a/stream".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn missing_player_is_reported() {
+        assert_eq!(
+            open_in_external_player("https://a/stream", Some("cradio-no-such-player")),
+            Err("No external player available: cradio-no-such-player not found".to_string())
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use ratatui::layout::Rect;
+use ratatui_image::{
+    Resize,
+    picker::{Picker, ProtocolType},
+    protocol::Protocol,
+};
+
+pub const FAVICON_SIZE: Rect = Rect {
+    x: 0,
+    y: 0,
+    width: 12,
+    height: 6,
+};
+
+pub struct FaviconRenderer {
+    picker: Picker,
+}
+
+impl FaviconRenderer {
+    pub fn detect() -> Option<Self> {
+        let picker = Picker::from_query_stdio().ok()?;
+        if picker.protocol_type() == ProtocolType::Halfblocks {
+            return None;
+        }
+        Some(Self { picker })
+    }
+
+    pub fn protocol(&self, bytes: &[u8]) -> Result<Protocol, String> {
+        let image =
+            image::load_from_memory(bytes).map_err(|e| format!("Invalid favicon image: {}", e))?;
+        self.picker
+            .new_protocol(image, FAVICON_SIZE, Resize::Fit(None))
+            .map_err(|e| format!("Failed to render favicon: {}", e))
+    }
+}
+
+pub async fn fetch_favicon(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, String> {
+    let response = client
+        .get(url)
+        .header("User-Agent", "cradio/0.1")
+        .send()
+        .await
+        .map_err(|e| format!("Favicon request failed: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Favicon error: {}", response.status()));
+    }
+
+    response
+        .bytes()
+        .await
+        .map(|bytes| bytes.to_vec())
+        .map_err(|e| format!("Favicon download failed: {}", e))
+}
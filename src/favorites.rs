@@ -1,35 +1,133 @@
 use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashSet},
     fs,
     path::{Path, PathBuf},
 };
 
-use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 
+use crate::{api::Station, paths};
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FavoriteEntry {
     pub stationuuid: String,
     pub name: String,
     pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url_override: Option<String>,
 }
 
-fn favorites_path() -> Result<PathBuf, String> {
-    let dirs = ProjectDirs::from("", "", "cradio")
-        .ok_or_else(|| "Unable to determine a config directory for this platform".to_string())?;
-    Ok(dirs.config_dir().join("favorites.json"))
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TieBreak {
+    #[default]
+    Uuid,
+    Url,
 }
 
-fn load_favorites_from_path(path: &Path) -> Result<Vec<FavoriteEntry>, String> {
-    if !path.exists() {
-        return Ok(Vec::new());
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextSeparator {
+    #[default]
+    Newline,
+    Comma,
+}
+
+impl TextSeparator {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Newline => "\n",
+            Self::Comma => ",",
+        }
     }
 
-    let content = fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read favorites file {}: {}", path.display(), e))?;
+    fn field_separator(self) -> &'static str {
+        match self {
+            Self::Newline => "\t",
+            Self::Comma => ",",
+        }
+    }
+}
 
-    let entries: Vec<FavoriteEntry> = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse favorites JSON {}: {}", path.display(), e))?;
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum FavoritesFile {
+    List(Vec<FavoriteEntry>),
+    Wrapped { favorites: Vec<FavoriteEntry> },
+}
 
+impl FavoritesFile {
+    fn into_entries(self) -> Vec<FavoriteEntry> {
+        match self {
+            Self::List(entries) | Self::Wrapped { favorites: entries } => entries,
+        }
+    }
+}
+
+impl FavoriteEntry {
+    pub fn to_cached_station(&self) -> Station {
+        Station {
+            stationuuid: self.stationuuid.clone(),
+            name: self.name.clone(),
+            url: self.url.clone(),
+            url_resolved: String::new(),
+            tags: String::new(),
+            country_code: String::new(),
+            language: String::new(),
+            bitrate: 0,
+            favicon: String::new(),
+            votes: 0,
+            codec: String::new(),
+            homepage: String::new(),
+        }
+    }
+}
+
+pub fn changed_upstream(favorites: &[FavoriteEntry], stations: &[Station]) -> Vec<FavoriteEntry> {
+    favorites
+        .iter()
+        .filter_map(|entry| {
+            let station = stations
+                .iter()
+                .find(|station| station.stationuuid == entry.stationuuid)?;
+            if station.name == entry.name && station.url == entry.url {
+                return None;
+            }
+            Some(FavoriteEntry {
+                stationuuid: entry.stationuuid.clone(),
+                name: station.name.clone(),
+                url: station.url.clone(),
+                url_override: entry.url_override.clone(),
+            })
+        })
+        .collect()
+}
+
+pub fn fallback_stations_from_cached(
+    favorites: &[FavoriteEntry],
+    failed_uuids: &[String],
+) -> Vec<Station> {
+    let failed_set: HashSet<&str> = failed_uuids.iter().map(String::as_str).collect();
+
+    favorites
+        .iter()
+        .filter(|fav| failed_set.contains(fav.stationuuid.as_str()))
+        .map(FavoriteEntry::to_cached_station)
+        .collect()
+}
+
+fn compare_favorites(a: &FavoriteEntry, b: &FavoriteEntry, tie_break: TieBreak) -> Ordering {
+    let by_name = a.name.to_lowercase().cmp(&b.name.to_lowercase());
+    match tie_break {
+        TieBreak::Uuid => by_name.then_with(|| a.stationuuid.cmp(&b.stationuuid)),
+        TieBreak::Url => by_name
+            .then_with(|| a.url.cmp(&b.url))
+            .then_with(|| a.stationuuid.cmp(&b.stationuuid)),
+    }
+}
+
+fn dedup_by_uuid(entries: impl IntoIterator<Item = FavoriteEntry>) -> Vec<FavoriteEntry> {
     let mut deduped: Vec<FavoriteEntry> = Vec::new();
     for entry in entries {
         if entry.stationuuid.trim().is_empty() {
@@ -41,22 +139,51 @@ fn load_favorites_from_path(path: &Path) -> Result<Vec<FavoriteEntry>, String> {
         {
             existing.name = entry.name;
             existing.url = entry.url;
+            existing.url_override = entry.url_override;
         } else {
             deduped.push(entry);
         }
     }
+    deduped
+}
 
-    deduped.sort_by(|a, b| {
-        a.name
-            .to_lowercase()
-            .cmp(&b.name.to_lowercase())
-            .then_with(|| a.stationuuid.cmp(&b.stationuuid))
-    });
+fn favorites_path() -> Result<PathBuf, String> {
+    Ok(paths::config_dir()?.join("favorites.json"))
+}
+
+fn load_favorites_from_path(
+    path: &Path,
+    tie_break: TieBreak,
+) -> Result<Vec<FavoriteEntry>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read favorites file {}: {}", path.display(), e))?;
+
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse favorites JSON {}: {}", path.display(), e))?;
+    let entries = serde_json::from_value::<FavoritesFile>(value)
+        .map_err(|_| {
+            format!(
+                "Failed to parse favorites JSON {}: expected an array of favorites or an object with a \"favorites\" array",
+                path.display()
+            )
+        })?
+        .into_entries();
+
+    let mut deduped = dedup_by_uuid(entries);
+    deduped.sort_by(|a, b| compare_favorites(a, b, tie_break));
 
     Ok(deduped)
 }
 
-fn save_favorites_to_path(path: &Path, favorites: &[FavoriteEntry]) -> Result<(), String> {
+fn save_favorites_to_path(
+    path: &Path,
+    favorites: &[FavoriteEntry],
+    tie_break: TieBreak,
+) -> Result<(), String> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| {
             format!(
@@ -67,49 +194,179 @@ fn save_favorites_to_path(path: &Path, favorites: &[FavoriteEntry]) -> Result<()
         })?;
     }
 
-    let mut deduped: Vec<FavoriteEntry> = Vec::new();
-    for entry in favorites {
-        if entry.stationuuid.trim().is_empty() {
+    let mut deduped = dedup_by_uuid(favorites.iter().cloned());
+    deduped.sort_by(|a, b| compare_favorites(a, b, tie_break));
+
+    let json = serde_json::to_string_pretty(&deduped)
+        .map_err(|e| format!("Failed to serialize favorites: {}", e))?;
+
+    fs::write(path, json)
+        .map_err(|e| format!("Failed to write favorites file {}: {}", path.display(), e))
+}
+
+pub fn load_favorites(tie_break: TieBreak) -> Result<Vec<FavoriteEntry>, String> {
+    let path = favorites_path()?;
+    load_favorites_from_path(&path, tie_break)
+}
+
+pub fn save_favorites(favorites: &[FavoriteEntry], tie_break: TieBreak) -> Result<(), String> {
+    let path = favorites_path()?;
+    save_favorites_to_path(&path, favorites, tie_break)
+}
+
+fn url_station_uuid(url: &str) -> String {
+    let hash = url.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("url:{:016x}", hash)
+}
+
+fn playlist_entry(url: &str, title: Option<&str>) -> Option<FavoriteEntry> {
+    let url = url.trim();
+    let parsed = reqwest::Url::parse(url).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return None;
+    }
+    let name = title
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .unwrap_or(url);
+    Some(FavoriteEntry {
+        stationuuid: url_station_uuid(url),
+        name: name.to_string(),
+        url: url.to_string(),
+        url_override: None,
+    })
+}
+
+fn parse_m3u(content: &str) -> Vec<FavoriteEntry> {
+    let mut entries = Vec::new();
+    let mut title: Option<String> = None;
+    for line in content.lines().map(str::trim) {
+        if let Some(info) = line.strip_prefix("#EXTINF:") {
+            title = info.split_once(',').map(|(_, name)| name.to_string());
+        } else if line.is_empty() || line.starts_with('#') {
             continue;
+        } else {
+            entries.extend(playlist_entry(line, title.take().as_deref()));
         }
-        if let Some(existing) = deduped
-            .iter_mut()
-            .find(|fav| fav.stationuuid == entry.stationuuid)
-        {
-            existing.name = entry.name.clone();
-            existing.url = entry.url.clone();
+    }
+    entries
+}
+
+fn parse_pls(content: &str) -> Vec<FavoriteEntry> {
+    let mut files: BTreeMap<u32, (Option<String>, Option<String>)> = BTreeMap::new();
+    for line in content.lines().map(str::trim) {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let (slot, index) = if let Some(index) = key.strip_prefix("file") {
+            (0, index)
+        } else if let Some(index) = key.strip_prefix("title") {
+            (1, index)
+        } else {
+            continue;
+        };
+        let Ok(index) = index.parse::<u32>() else {
+            continue;
+        };
+        let entry = files.entry(index).or_default();
+        if slot == 0 {
+            entry.0 = Some(value.trim().to_string());
         } else {
-            deduped.push(entry.clone());
+            entry.1 = Some(value.trim().to_string());
         }
     }
+    files
+        .into_values()
+        .filter_map(|(url, title)| playlist_entry(&url?, title.as_deref()))
+        .collect()
+}
 
-    deduped.sort_by(|a, b| {
-        a.name
-            .to_lowercase()
-            .cmp(&b.name.to_lowercase())
-            .then_with(|| a.stationuuid.cmp(&b.stationuuid))
+pub fn import_playlist(path: &Path) -> Result<Vec<FavoriteEntry>, String> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    if !matches!(extension.as_str(), "m3u" | "m3u8" | "pls") {
+        return Err(format!(
+            "Unsupported playlist {}: expected a .m3u, .m3u8 or .pls file",
+            path.display()
+        ));
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read playlist {}: {}", path.display(), e))?;
+    if extension == "pls" {
+        Ok(parse_pls(&content))
+    } else {
+        Ok(parse_m3u(&content))
+    }
+}
+
+pub fn merge_favorites(
+    favorites: &[FavoriteEntry],
+    imported: Vec<FavoriteEntry>,
+) -> Vec<FavoriteEntry> {
+    let new_entries = imported.into_iter().filter(|entry| {
+        !favorites
+            .iter()
+            .any(|fav| fav.stationuuid == entry.stationuuid || fav.url == entry.url)
     });
+    dedup_by_uuid(favorites.iter().cloned().chain(new_entries))
+}
 
-    let json = serde_json::to_string_pretty(&deduped)
-        .map_err(|e| format!("Failed to serialize favorites: {}", e))?;
+fn m3u_playlist(favorites: &[FavoriteEntry]) -> String {
+    let mut playlist = String::from("#EXTM3U\n");
+    for entry in favorites {
+        let url = entry.url_override.as_deref().unwrap_or(&entry.url);
+        playlist.push_str(&format!("#EXTINF:-1,{}\n{}\n", entry.name, url));
+    }
+    playlist
+}
 
-    fs::write(path, json)
-        .map_err(|e| format!("Failed to write favorites file {}: {}", path.display(), e))
+pub fn export_m3u(favorites: &[FavoriteEntry], path: &Path) -> Result<(), String> {
+    fs::write(path, m3u_playlist(favorites))
+        .map_err(|e| format!("Failed to write playlist {}: {}", path.display(), e))
 }
 
-pub fn load_favorites() -> Result<Vec<FavoriteEntry>, String> {
-    let path = favorites_path()?;
-    load_favorites_from_path(&path)
+fn text_list(favorites: &[FavoriteEntry], include_names: bool, separator: TextSeparator) -> String {
+    let items: Vec<String> = favorites
+        .iter()
+        .map(|entry| {
+            let url = entry.url_override.as_deref().unwrap_or(&entry.url);
+            if include_names {
+                format!("{}{}{}", entry.name, separator.field_separator(), url)
+            } else {
+                url.to_string()
+            }
+        })
+        .collect();
+    let mut list = items.join(separator.as_str());
+    if !list.is_empty() {
+        list.push('\n');
+    }
+    list
 }
 
-pub fn save_favorites(favorites: &[FavoriteEntry]) -> Result<(), String> {
-    let path = favorites_path()?;
-    save_favorites_to_path(&path, favorites)
+pub fn export_txt(
+    favorites: &[FavoriteEntry],
+    path: &Path,
+    include_names: bool,
+    separator: TextSeparator,
+) -> Result<(), String> {
+    fs::write(path, text_list(favorites, include_names, separator))
+        .map_err(|e| format!("Failed to write list {}: {}", path.display(), e))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{FavoriteEntry, favorites_path, load_favorites_from_path, save_favorites_to_path};
+    use super::{
+        FavoriteEntry, TextSeparator, TieBreak, changed_upstream, export_m3u, export_txt,
+        fallback_stations_from_cached, favorites_path, import_playlist, load_favorites_from_path,
+        m3u_playlist, merge_favorites, parse_m3u, parse_pls, save_favorites_to_path, text_list,
+    };
     use std::{
         fs,
         path::{Path, PathBuf},
@@ -143,13 +400,15 @@ mod tests {
             stationuuid: id.to_string(),
             name: name.to_string(),
             url: url.to_string(),
+            url_override: None,
         }
     }
 
     #[test]
     fn load_missing_file_returns_empty_vec() {
         let path = temp_path("missing");
-        let favorites = load_favorites_from_path(&path).expect("load should succeed");
+        let favorites =
+            load_favorites_from_path(&path, TieBreak::Uuid).expect("load should succeed");
         assert!(favorites.is_empty());
     }
 
@@ -159,8 +418,114 @@ mod tests {
         fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
         fs::write(&path, "{not-json]").expect("write invalid json");
 
-        let err = load_favorites_from_path(&path).expect_err("expected parse error");
+        let err =
+            load_favorites_from_path(&path, TieBreak::Uuid).expect_err("expected parse error");
+        assert!(err.contains("Failed to parse favorites JSON"));
+
+        cleanup(&path);
+    }
+
+    #[test]
+    fn load_accepts_bare_array() {
+        let path = temp_path("bare-array");
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        fs::write(
+            &path,
+            r#"[{"stationuuid":"uuid-a","name":"Alpha","url":"https://a"}]"#,
+        )
+        .expect("write favorites");
+
+        let loaded =
+            load_favorites_from_path(&path, TieBreak::Uuid).expect("bare array should load");
+        assert_eq!(loaded, vec![fav("uuid-a", "Alpha", "https://a")]);
+
+        cleanup(&path);
+    }
+
+    #[test]
+    fn load_accepts_object_with_favorites_key() {
+        let path = temp_path("wrapped");
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        fs::write(
+            &path,
+            r#"{"favorites":[{"stationuuid":"uuid-a","name":"Alpha","url":"https://a"}]}"#,
+        )
+        .expect("write favorites");
+
+        let loaded =
+            load_favorites_from_path(&path, TieBreak::Uuid).expect("wrapped object should load");
+        assert_eq!(loaded, vec![fav("uuid-a", "Alpha", "https://a")]);
+
+        cleanup(&path);
+    }
+
+    #[test]
+    fn load_accepts_versioned_object() {
+        let path = temp_path("versioned");
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        fs::write(
+            &path,
+            r#"{"version":2,"favorites":[{"stationuuid":"uuid-a","name":"Alpha","url":"https://a"}]}"#,
+        )
+        .expect("write favorites");
+
+        let loaded =
+            load_favorites_from_path(&path, TieBreak::Uuid).expect("versioned object should load");
+        assert_eq!(loaded, vec![fav("uuid-a", "Alpha", "https://a")]);
+
+        cleanup(&path);
+    }
+
+    #[test]
+    fn same_named_favorites_follow_the_configured_tie_break() {
+        let path = temp_path("tie-break");
+        let favorites = vec![
+            fav("uuid-a", "Jazz", "https://z"),
+            fav("uuid-b", "jazz", "https://a"),
+        ];
+
+        save_favorites_to_path(&path, &favorites, TieBreak::Uuid).expect("save should work");
+        let by_uuid = load_favorites_from_path(&path, TieBreak::Uuid).expect("load");
+        assert_eq!(by_uuid[0].stationuuid, "uuid-a");
+
+        let by_url = load_favorites_from_path(&path, TieBreak::Url).expect("load");
+        assert_eq!(by_url[0].stationuuid, "uuid-b");
+
+        save_favorites_to_path(&path, &favorites, TieBreak::Url).expect("save should work");
+        let json = fs::read_to_string(&path).expect("read favorites");
+        assert!(json.find("uuid-b") < json.find("uuid-a"));
+
+        cleanup(&path);
+    }
+
+    #[test]
+    fn url_override_roundtrips_and_is_omitted_when_unset() {
+        let path = temp_path("url-override");
+        let mut pinned = fav("uuid-a", "Alpha", "https://a");
+        pinned.url_override = Some("https://direct.a/stream".to_string());
+        let favorites = vec![pinned.clone(), fav("uuid-b", "Beta", "https://b")];
+
+        save_favorites_to_path(&path, &favorites, TieBreak::Uuid).expect("save should work");
+        let json = fs::read_to_string(&path).expect("read favorites");
+        assert_eq!(json.matches("url_override").count(), 1);
+
+        let loaded = load_favorites_from_path(&path, TieBreak::Uuid).expect("load should work");
+        assert_eq!(loaded, favorites);
+        assert!(m3u_playlist(&loaded).contains("#EXTINF:-1,Alpha\nhttps://direct.a/stream\n"));
+
+        cleanup(&path);
+    }
+
+    #[test]
+    fn load_unrecognized_shape_returns_clear_error() {
+        let path = temp_path("wrong-shape");
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        fs::write(&path, r#"{"stations":[]}"#).expect("write favorites");
+
+        let err =
+            load_favorites_from_path(&path, TieBreak::Uuid).expect_err("unknown shape should fail");
         assert!(err.contains("Failed to parse favorites JSON"));
+        assert!(err.contains("expected an array of favorites"));
 
         cleanup(&path);
     }
@@ -173,8 +538,8 @@ mod tests {
             fav("uuid-a", "Alpha", "https://a"),
         ];
 
-        save_favorites_to_path(&path, &favorites).expect("save should work");
-        let loaded = load_favorites_from_path(&path).expect("load should work");
+        save_favorites_to_path(&path, &favorites, TieBreak::Uuid).expect("save should work");
+        let loaded = load_favorites_from_path(&path, TieBreak::Uuid).expect("load should work");
 
         assert_eq!(loaded.len(), 2);
         assert_eq!(loaded[0].stationuuid, "uuid-a");
@@ -192,8 +557,8 @@ mod tests {
             fav("uuid-b", "Beta", "https://b"),
         ];
 
-        save_favorites_to_path(&path, &favorites).expect("save should work");
-        let loaded = load_favorites_from_path(&path).expect("load should work");
+        save_favorites_to_path(&path, &favorites, TieBreak::Uuid).expect("save should work");
+        let loaded = load_favorites_from_path(&path, TieBreak::Uuid).expect("load should work");
 
         assert_eq!(loaded.len(), 2);
         let updated = loaded
@@ -211,12 +576,233 @@ mod tests {
         let path = temp_path("mkdirs");
         let favorites = vec![fav("uuid-a", "Alpha", "https://a")];
 
-        save_favorites_to_path(&path, &favorites).expect("save should create parent directories");
+        save_favorites_to_path(&path, &favorites, TieBreak::Uuid)
+            .expect("save should create parent directories");
 
         assert!(path.exists());
         cleanup(&path);
     }
 
+    #[test]
+    fn changed_upstream_reports_name_and_url_changes_only() {
+        let favorites = vec![
+            fav("uuid-a", "Alpha", "https://a"),
+            fav("uuid-b", "Beta", "https://b"),
+            fav("uuid-c", "Gamma", "https://c"),
+        ];
+        let mut renamed = fav("uuid-a", "Alpha FM", "https://a").to_cached_station();
+        renamed.tags = "jazz".to_string();
+        let stations = vec![
+            renamed,
+            fav("uuid-b", "Beta", "https://b").to_cached_station(),
+            fav("uuid-c", "Gamma", "https://c2").to_cached_station(),
+        ];
+
+        let changes = changed_upstream(&favorites, &stations);
+
+        assert_eq!(
+            changes,
+            vec![
+                fav("uuid-a", "Alpha FM", "https://a"),
+                fav("uuid-c", "Gamma", "https://c2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn fallback_stations_cover_only_failed_favorites_and_stay_playable() {
+        let favorites = vec![
+            fav("uuid-a", "Alpha", "https://a/stream"),
+            fav("uuid-b", "Beta", "https://b/stream"),
+            fav("uuid-c", "Gamma", "https://c/stream"),
+        ];
+        let failed = vec![
+            "uuid-c".to_string(),
+            "uuid-a".to_string(),
+            "uuid-x".to_string(),
+        ];
+
+        let stations = fallback_stations_from_cached(&favorites, &failed);
+
+        let summary: Vec<(&str, &str, &str)> = stations
+            .iter()
+            .map(|s| (s.stationuuid.as_str(), s.name.as_str(), s.url.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("uuid-a", "Alpha", "https://a/stream"),
+                ("uuid-c", "Gamma", "https://c/stream"),
+            ]
+        );
+        assert!(stations.iter().all(|s| s.url_resolved.is_empty()));
+        assert!(fallback_stations_from_cached(&favorites, &[]).is_empty());
+    }
+
+    #[test]
+    fn m3u_playlist_lists_each_favorite() {
+        let favorites = vec![
+            fav("uuid-a", "Alpha", "https://a"),
+            fav("uuid-b", "Beta", "https://b"),
+        ];
+
+        assert_eq!(
+            m3u_playlist(&favorites),
+            "#EXTM3U\n#EXTINF:-1,Alpha\nhttps://a\n#EXTINF:-1,Beta\nhttps://b\n"
+        );
+    }
+
+    #[test]
+    fn export_m3u_writes_playlist_file() {
+        let dir = temp_dir("m3u");
+        fs::create_dir_all(&dir).expect("create dir");
+        let path = dir.join("favorites.m3u");
+
+        export_m3u(&[fav("uuid-a", "Alpha", "https://a")], &path).expect("export should work");
+
+        let content = fs::read_to_string(&path).expect("read playlist");
+        assert!(content.starts_with("#EXTM3U\n"));
+        assert!(content.contains("https://a"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn text_list_uses_the_configured_separator() {
+        let mut direct = fav("uuid-b", "Beta", "https://b");
+        direct.url_override = Some("https://direct.b".to_string());
+        let favorites = vec![fav("uuid-a", "Alpha", "https://a"), direct];
+
+        assert_eq!(
+            text_list(&favorites, false, TextSeparator::Newline),
+            "https://a\nhttps://direct.b\n"
+        );
+        assert_eq!(
+            text_list(&favorites, false, TextSeparator::Comma),
+            "https://a,https://direct.b\n"
+        );
+        assert_eq!(
+            text_list(&favorites, true, TextSeparator::Newline),
+            "Alpha\thttps://a\nBeta\thttps://direct.b\n"
+        );
+        assert_eq!(
+            text_list(&favorites, true, TextSeparator::Comma),
+            "Alpha,https://a,Beta,https://direct.b\n"
+        );
+        assert_eq!(text_list(&[], false, TextSeparator::Comma), "");
+    }
+
+    #[test]
+    fn export_txt_writes_list_file() {
+        let dir = temp_dir("txt");
+        fs::create_dir_all(&dir).expect("create dir");
+        let path = dir.join("favorites.txt");
+
+        export_txt(
+            &[fav("uuid-a", "Alpha", "https://a")],
+            &path,
+            true,
+            TextSeparator::Comma,
+        )
+        .expect("export should work");
+
+        let content = fs::read_to_string(&path).expect("read list");
+        assert_eq!(content, "Alpha,https://a\n");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parse_m3u_reads_titles_and_skips_malformed_lines() {
+        let entries = parse_m3u(
+            "#EXTM3U\n#EXTINF:-1,Alpha FM\nhttps://a.example/live\nnot a url\n\nhttp://b.example:8000\n",
+        );
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "Alpha FM");
+        assert_eq!(entries[0].url, "https://a.example/live");
+        assert!(entries[0].stationuuid.starts_with("url:"));
+        assert_eq!(entries[1].name, "http://b.example:8000");
+        assert_ne!(entries[0].stationuuid, entries[1].stationuuid);
+        assert_eq!(
+            parse_m3u("https://a.example/live")[0].stationuuid,
+            entries[0].stationuuid
+        );
+    }
+
+    #[test]
+    fn parse_pls_pairs_files_with_titles() {
+        let entries = parse_pls(
+            "[playlist]\nNumberOfEntries=3\nFile2=https://b.example\nTitle2=Beta\nFile1=https://a.example\nFilex=https://x.example\nFile3=ftp://c.example\nbroken line\nVersion=2\n",
+        );
+
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["https://a.example", "Beta"]);
+    }
+
+    #[test]
+    fn import_playlist_detects_the_format_by_extension() {
+        let dir = temp_dir("import");
+        fs::create_dir_all(&dir).expect("create dir");
+        let pls = dir.join("list.PLS");
+        fs::write(&pls, "[playlist]\nFile1=https://a.example\nTitle1=Alpha\n").expect("write");
+        let m3u = dir.join("list.m3u8");
+        fs::write(&m3u, "#EXTINF:-1,Beta\nhttps://b.example\n").expect("write");
+
+        assert_eq!(import_playlist(&pls).expect("pls")[0].name, "Alpha");
+        assert_eq!(import_playlist(&m3u).expect("m3u")[0].name, "Beta");
+        assert!(import_playlist(&dir.join("list.txt")).is_err());
+        assert!(import_playlist(&dir.join("missing.m3u")).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn merge_favorites_skips_entries_already_stored() {
+        let mut existing = fav("uuid-a", "Alpha", "https://a");
+        existing.url_override = Some("https://direct.a".to_string());
+        let imported = parse_m3u("#EXTINF:-1,Renamed\nhttps://a\nhttps://b\nhttps://b\n");
+
+        let merged = merge_favorites(&[existing.clone()], imported);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0], existing);
+        assert_eq!(merged[1].url, "https://b");
+    }
+
+    #[test]
+    fn save_and_load_roundtrip_in_unicode_directory_with_spaces() {
+        let dir = temp_dir("unicode").join("Müller Ångström 音楽");
+        let path = dir.join("favorites.json");
+        let favorites = vec![fav("uuid-a", "Ålpha", "https://a")];
+
+        save_favorites_to_path(&path, &favorites, TieBreak::Uuid).expect("save should work");
+        let loaded = load_favorites_from_path(&path, TieBreak::Uuid).expect("load should work");
+
+        assert_eq!(loaded, favorites);
+        let _ = fs::remove_dir_all(dir.parent().expect("parent"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_directory_is_supported_and_reported_lossily() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let dir = temp_dir("non-utf8").join(OsStr::from_bytes(b"home-\xff-dir"));
+        let path = dir.join("favorites.json");
+        let favorites = vec![fav("uuid-a", "Alpha", "https://a")];
+
+        save_favorites_to_path(&path, &favorites, TieBreak::Uuid).expect("save should work");
+        assert_eq!(
+            load_favorites_from_path(&path, TieBreak::Uuid).expect("load should work"),
+            favorites
+        );
+
+        fs::write(&path, "{not-json]").expect("write invalid json");
+        let err =
+            load_favorites_from_path(&path, TieBreak::Uuid).expect_err("expected parse error");
+        assert!(err.contains("home-\u{fffd}-dir"));
+
+        let _ = fs::remove_dir_all(dir.parent().expect("parent"));
+    }
+
     #[test]
     fn favorites_path_uses_native_config_directory() {
         let path = favorites_path().expect("native config path should resolve");
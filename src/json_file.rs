@@ -0,0 +1,71 @@
+use std::{fs, path::Path};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+pub fn load<T: DeserializeOwned>(path: &Path, label: &str) -> Result<Option<T>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {} file {}: {}", label, path.display(), e))?;
+
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse {} JSON {}: {}", label, path.display(), e))
+}
+
+pub fn save<T: Serialize>(path: &Path, value: &T, label: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            format!(
+                "Failed to create {} directory {}: {}",
+                label,
+                parent.display(),
+                e
+            )
+        })?;
+    }
+
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", label, e))?;
+
+    fs::write(path, json)
+        .map_err(|e| format!("Failed to write {} file {}: {}", label, path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load, save};
+    use std::{collections::BTreeMap, fs, path::PathBuf};
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("cradio-json-test-{}-{}", name, std::process::id()))
+            .join("nested")
+            .join("data.json")
+    }
+
+    #[test]
+    fn values_roundtrip_and_missing_files_load_as_none() {
+        let path = temp_path("roundtrip");
+        assert_eq!(load::<BTreeMap<String, u32>>(&path, "test"), Ok(None));
+
+        let value = BTreeMap::from([("jazz".to_string(), 3)]);
+        save(&path, &value, "test").expect("save should create parent directories");
+
+        assert_eq!(load(&path, "test"), Ok(Some(value)));
+        let _ = fs::remove_dir_all(path.parent().and_then(|p| p.parent()).expect("parent"));
+    }
+
+    #[test]
+    fn invalid_json_is_reported_with_the_label() {
+        let path = temp_path("invalid");
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        fs::write(&path, "[1,2").expect("write file");
+
+        let err = load::<Vec<u32>>(&path, "stats").expect_err("invalid json");
+        assert!(err.starts_with("Failed to parse stats JSON"));
+        let _ = fs::remove_dir_all(path.parent().and_then(|p| p.parent()).expect("parent"));
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crossterm::event::KeyCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Action {
+    Quit,
+    Stop,
+    Pause,
+    Reconnect,
+    Forget,
+    Filter,
+    QuickFilter,
+    Command,
+    Favorite,
+    Favorites,
+    MostPlayed,
+    History,
+    RefreshFavorites,
+    CachedFavorites,
+    UpdateFavorites,
+    Details,
+    FindSimilar,
+    Share,
+    Vote,
+    CopyTrack,
+    Mini,
+    External,
+    Record,
+    NextPage,
+    PrevPage,
+    Reverse,
+    Sort,
+    Codec,
+    ToggleRange,
+    PreferResolved,
+    VolumeUp,
+    VolumeDown,
+    Help,
+}
+
+const ACTIONS: [Action; 33] = [
+    Action::Quit,
+    Action::Stop,
+    Action::Pause,
+    Action::Reconnect,
+    Action::Forget,
+    Action::Filter,
+    Action::QuickFilter,
+    Action::Command,
+    Action::Favorite,
+    Action::Favorites,
+    Action::MostPlayed,
+    Action::History,
+    Action::RefreshFavorites,
+    Action::CachedFavorites,
+    Action::UpdateFavorites,
+    Action::Details,
+    Action::FindSimilar,
+    Action::Share,
+    Action::Vote,
+    Action::CopyTrack,
+    Action::Mini,
+    Action::External,
+    Action::Record,
+    Action::NextPage,
+    Action::PrevPage,
+    Action::Reverse,
+    Action::Sort,
+    Action::Codec,
+    Action::ToggleRange,
+    Action::PreferResolved,
+    Action::VolumeUp,
+    Action::VolumeDown,
+    Action::Help,
+];
+
+impl Action {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::Stop => "stop",
+            Self::Pause => "pause",
+            Self::Reconnect => "reconnect",
+            Self::Forget => "forget",
+            Self::Filter => "filter",
+            Self::QuickFilter => "quick_filter",
+            Self::Command => "command",
+            Self::Favorite => "favorite",
+            Self::Favorites => "favorites",
+            Self::MostPlayed => "most_played",
+            Self::History => "history",
+            Self::RefreshFavorites => "refresh_favorites",
+            Self::CachedFavorites => "cached_favorites",
+            Self::UpdateFavorites => "update_favorites",
+            Self::Details => "details",
+            Self::FindSimilar => "find_similar",
+            Self::Share => "share",
+            Self::Vote => "vote",
+            Self::CopyTrack => "copy_track",
+            Self::Mini => "mini",
+            Self::External => "external",
+            Self::Record => "record",
+            Self::NextPage => "next_page",
+            Self::PrevPage => "prev_page",
+            Self::Reverse => "reverse",
+            Self::Sort => "sort",
+            Self::Codec => "codec",
+            Self::ToggleRange => "toggle_range",
+            Self::PreferResolved => "prefer_resolved",
+            Self::VolumeUp => "volume_up",
+            Self::VolumeDown => "volume_down",
+            Self::Help => "help",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        ACTIONS.into_iter().find(|action| action.name() == name)
+    }
+
+    fn default_key(self) -> char {
+        match self {
+            Self::Quit => 'q',
+            Self::Stop => 's',
+            Self::Pause => 'c',
+            Self::Reconnect => 'R',
+            Self::Forget => 'F',
+            Self::Filter => '/',
+            Self::QuickFilter => '&',
+            Self::Command => ':',
+            Self::Favorite => ' ',
+            Self::Favorites => 'f',
+            Self::MostPlayed => 'M',
+            Self::History => 'h',
+            Self::RefreshFavorites => 'l',
+            Self::CachedFavorites => 'L',
+            Self::UpdateFavorites => 'u',
+            Self::Details => 'i',
+            Self::FindSimilar => 'S',
+            Self::Share => 'x',
+            Self::Vote => 'v',
+            Self::CopyTrack => 'y',
+            Self::Mini => 'm',
+            Self::External => 'e',
+            Self::Record => 'w',
+            Self::NextPage => 'n',
+            Self::PrevPage => 'p',
+            Self::Reverse => 'r',
+            Self::Sort => 'o',
+            Self::Codec => 'g',
+            Self::ToggleRange => '#',
+            Self::PreferResolved => 'U',
+            Self::VolumeUp => '+',
+            Self::VolumeDown => '-',
+            Self::Help => '?',
+        }
+    }
+}
+
+fn parse_key(value: &str) -> Result<char, String> {
+    if value.eq_ignore_ascii_case("space") {
+        return Ok(' ');
+    }
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(format!(
+            "Invalid key {:?}: use a single character or \"space\"",
+            value
+        )),
+    }
+}
+
+fn key_label(key: char) -> String {
+    if key == ' ' {
+        "Space".to_string()
+    } else {
+        key.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyMap {
+    keys: BTreeMap<Action, char>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            keys: ACTIONS
+                .into_iter()
+                .map(|action| (action, action.default_key()))
+                .collect(),
+        }
+    }
+}
+
+impl KeyMap {
+    pub fn from_bindings(bindings: &BTreeMap<String, String>) -> Result<Self, String> {
+        let mut keymap = Self::default();
+        for (name, value) in bindings {
+            let action = Action::from_name(name)
+                .ok_or_else(|| format!("Unknown key binding action: {}", name))?;
+            let key = parse_key(value)?;
+            if key.is_ascii_digit() && key != '0' {
+                return Err(format!(
+                    "Key '{}' for {} is reserved for favoriting visible rows",
+                    key, name
+                ));
+            }
+            keymap.keys.insert(action, key);
+        }
+
+        let mut owners: HashMap<char, Vec<&str>> = HashMap::new();
+        for (action, key) in &keymap.keys {
+            owners.entry(*key).or_default().push(action.name());
+        }
+        let mut conflicts: Vec<String> = owners
+            .into_iter()
+            .filter(|(_, actions)| actions.len() > 1)
+            .map(|(key, actions)| format!("'{}' ({})", key_label(key), actions.join(", ")))
+            .collect();
+        if !conflicts.is_empty() {
+            conflicts.sort();
+            return Err(format!(
+                "Conflicting key bindings: {}",
+                conflicts.join("; ")
+            ));
+        }
+
+        Ok(keymap)
+    }
+
+    pub fn label(&self, action: Action) -> String {
+        key_label(self.keys[&action])
+    }
+
+    pub fn translate(&self, code: KeyCode) -> KeyCode {
+        let KeyCode::Char(c) = code else {
+            return code;
+        };
+        if let Some((action, _)) = self.keys.iter().find(|(_, key)| **key == c) {
+            return KeyCode::Char(action.default_key());
+        }
+        if ACTIONS.iter().any(|action| action.default_key() == c) {
+            return KeyCode::Null;
+        }
+        code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, KeyMap};
+    use crossterm::event::KeyCode;
+    use std::collections::BTreeMap;
+
+    fn bindings(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(action, key)| (action.to_string(), key.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn default_keymap_passes_keys_through() {
+        let keymap = KeyMap::from_bindings(&BTreeMap::new()).expect("defaults are valid");
+        assert_eq!(keymap, KeyMap::default());
+        assert_eq!(keymap.translate(KeyCode::Char('s')), KeyCode::Char('s'));
+        assert_eq!(keymap.translate(KeyCode::Char('z')), KeyCode::Char('z'));
+        assert_eq!(keymap.translate(KeyCode::Enter), KeyCode::Enter);
+        assert_eq!(keymap.label(Action::Favorite), "Space");
+    }
+
+    #[test]
+    fn remapped_keys_translate_to_the_default_action_key() {
+        let keymap = KeyMap::from_bindings(&bindings(&[("stop", "k"), ("favorite", "*")]))
+            .expect("valid bindings");
+
+        assert_eq!(keymap.translate(KeyCode::Char('k')), KeyCode::Char('s'));
+        assert_eq!(keymap.translate(KeyCode::Char('*')), KeyCode::Char(' '));
+        assert_eq!(keymap.translate(KeyCode::Char('s')), KeyCode::Null);
+        assert_eq!(keymap.translate(KeyCode::Char(' ')), KeyCode::Null);
+        assert_eq!(keymap.label(Action::Stop), "k");
+    }
+
+    #[test]
+    fn swapping_keys_between_actions_is_allowed() {
+        let keymap = KeyMap::from_bindings(&bindings(&[("next_page", "p"), ("prev_page", "n")]))
+            .expect("swap is not a conflict");
+
+        assert_eq!(keymap.translate(KeyCode::Char('p')), KeyCode::Char('n'));
+        assert_eq!(keymap.translate(KeyCode::Char('n')), KeyCode::Char('p'));
+    }
+
+    #[test]
+    fn conflicting_bindings_are_rejected_with_every_conflict_listed() {
+        let err = KeyMap::from_bindings(&bindings(&[("share", "s"), ("details", "space")]))
+            .expect_err("conflicts");
+
+        assert_eq!(
+            err,
+            "Conflicting key bindings: 'Space' (favorite, details); 's' (stop, share)"
+        );
+    }
+
+    #[test]
+    fn invalid_bindings_are_rejected() {
+        assert_eq!(
+            KeyMap::from_bindings(&bindings(&[("dance", "d")])),
+            Err("Unknown key binding action: dance".to_string())
+        );
+        assert!(KeyMap::from_bindings(&bindings(&[("stop", "ctrl+s")])).is_err());
+        assert!(KeyMap::from_bindings(&bindings(&[("stop", "3")])).is_err());
+    }
+}
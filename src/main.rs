@@ -1,56 +1,180 @@
 mod api;
 mod app;
+mod audio;
+mod cache;
+mod clipboard;
+mod command;
+mod external;
+#[cfg(feature = "favicons")]
+mod favicon;
 mod favorites;
+mod json_file;
+mod keymap;
+mod notification;
+mod paths;
 mod player;
+mod recording;
+mod release_notes;
+mod session;
+mod settings;
+mod state;
+mod stats;
 mod ui;
 
 use std::{
     collections::HashSet,
-    io,
-    time::{Duration, Instant},
+    io::{self, IsTerminal},
+    time::Instant,
 };
 
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyEventKind, KeyModifiers,
+    },
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    style::Print,
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, SetTitle, disable_raw_mode, enable_raw_mode,
+    },
+};
+use ratatui::{
+    Terminal, TerminalOptions, Viewport, backend::CrosstermBackend, widgets::TableState,
 };
-use ratatui::{Terminal, backend::CrosstermBackend, widgets::TableState};
 use tokio::sync::mpsc;
 
+use api::StationSource;
 use app::{App, AppMode, InputField, StationViewMode};
-use favorites::FavoriteEntry;
-use player::Player;
+use command::Command;
+use player::{PlaybackState, Player};
+use session::SessionSnapshot;
+use settings::Theme;
 
 #[derive(Debug)]
 enum AppEvent {
     StationsLoaded {
         request_id: u64,
         stations: Vec<api::Station>,
+        fetched: usize,
     },
     LoadError {
         request_id: u64,
         err: String,
     },
-    FavoritesLoaded(Vec<api::Station>, Vec<String>),
+    FavoritesLoaded(Vec<(String, api::Station)>, Vec<String>),
+    MostPlayedLoaded(Vec<(String, api::Station)>, Vec<String>),
+    SessionStationLoaded(Option<api::Station>),
+    StreamChecked {
+        station: api::Station,
+        result: Result<String, String>,
+    },
+    #[cfg(feature = "favicons")]
+    FaviconLoaded {
+        stationuuid: String,
+        bytes: Vec<u8>,
+    },
+    Voted {
+        name: String,
+        result: Result<(), String>,
+    },
+    #[cfg(unix)]
+    Hangup,
+}
+
+impl AppEvent {
+    fn is_network_response(&self) -> bool {
+        matches!(
+            self,
+            Self::StationsLoaded { .. }
+                | Self::LoadError { .. }
+                | Self::FavoritesLoaded(_, _)
+                | Self::MostPlayedLoaded(_, _)
+                | Self::SessionStationLoaded(_)
+                | Self::StreamChecked { .. }
+                | Self::Voted { .. }
+        )
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct CliOptions {
+    no_auto_search: bool,
+    no_altscreen: bool,
+}
+
+const INTERACTIVE_TERMINAL_REQUIRED: &str = "cradio requires an interactive terminal";
+const INLINE_VIEWPORT_HEIGHT: u16 = 24;
+const SEEK_UNSUPPORTED: &str = "This stream does not support seeking";
+const PUSH_WINDOW_TITLE: &str = "\x1b[22;0t";
+const POP_WINDOW_TITLE: &str = "\x1b[23;0t";
+
+impl CliOptions {
+    fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        for arg in args {
+            match arg.as_str() {
+                "--no-auto-search" => options.no_auto_search = true,
+                "--no-altscreen" => options.no_altscreen = true,
+                other => return Err(format!("Unknown argument: {}", other)),
+            }
+        }
+        Ok(options)
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), io::Error> {
-    enable_raw_mode()?;
+    let options = match CliOptions::from_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            std::process::exit(2);
+        }
+    };
+
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        eprintln!("Error: {}", INTERACTIVE_TERMINAL_REQUIRED);
+        std::process::exit(1);
+    }
+    if let Err(err) = enable_raw_mode() {
+        eprintln!("Error: {} ({})", INTERACTIVE_TERMINAL_REQUIRED, err);
+        std::process::exit(1);
+    }
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    if !options.no_altscreen
+        && let Err(err) = execute!(stdout, EnterAlternateScreen)
+    {
+        let _ = disable_raw_mode();
+        eprintln!(
+            "Error: {} with alternate screen support ({}). Try --no-altscreen to run inline.",
+            INTERACTIVE_TERMINAL_REQUIRED, err
+        );
+        std::process::exit(1);
+    }
+    execute!(stdout, EnableMouseCapture, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    let mut terminal = if options.no_altscreen {
+        Terminal::with_options(
+            backend,
+            TerminalOptions {
+                viewport: Viewport::Inline(INLINE_VIEWPORT_HEIGHT),
+            },
+        )?
+    } else {
+        Terminal::new(backend)?
+    };
 
-    let result = run_app(&mut terminal).await;
+    let result = run_app(&mut terminal, &options).await;
 
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste
     )?;
+    if !options.no_altscreen {
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    }
     terminal.show_cursor()?;
 
     if let Err(err) = result {
@@ -60,51 +184,145 @@ async fn main() -> Result<(), io::Error> {
     Ok(())
 }
 
-async fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<(), String> {
+async fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    options: &CliOptions,
+) -> Result<(), String> {
     let mut app = App::new();
+    app.no_color = ui::monochrome_from_env(
+        std::env::var("NO_COLOR").ok().as_deref(),
+        std::env::var("TERM").ok().as_deref(),
+    );
+    app.monochrome = app.no_color;
     let mut player = Player::new();
+    player.volume = state::load_state().volume;
+    app.volume = player.volume;
     let mut table_state = TableState::default();
+    #[cfg(feature = "favicons")]
+    let favicon_renderer = favicon::FaviconRenderer::detect();
+
+    match settings::load_settings() {
+        Ok(loaded) => {
+            app.settings_loaded = true;
+            if let Err(err) = app.apply_settings(loaded) {
+                app.set_status(err);
+            }
+            app.apply_default_filters();
+            if let Some(name) = app.settings.startup_search.clone()
+                && let Err(err) = app.apply_saved_search(&name)
+            {
+                app.set_status(format!("{}. Showing the default browse instead", err));
+            }
+            note_version_seen(&mut app);
+        }
+        Err(err) => app.set_error(err),
+    }
 
-    match favorites::load_favorites() {
-        Ok(entries) => app.set_favorites(entries),
+    match favorites::load_favorites(app.settings.favorites_tie_break) {
+        Ok(entries) => {
+            app.set_favorites(entries);
+            app.favorites_loaded = true;
+        }
+        Err(err) => app.set_error(err),
+    }
+    if let Some(warning) = app.favorites_limit_warning() {
+        app.set_status(warning);
+    }
+    match stats::load_stats() {
+        Ok(stats) => {
+            app.stats = stats;
+            app.stats_loaded = true;
+        }
         Err(err) => app.set_error(err),
     }
 
     let (tx, mut rx) = mpsc::unbounded_channel::<AppEvent>();
-    let http_client = reqwest::Client::new();
+    let http_client = api::build_client(&api::ApiConfig::default())?;
 
-    app.loading = true;
-    trigger_load(&tx, &http_client, &mut app);
+    trigger_resolve_servers(&http_client, &mut app);
+    #[cfg(unix)]
+    trigger_hangup_listener(&tx, &mut app);
+    if app.settings.auto_search && !options.no_auto_search {
+        app.loading = true;
+        trigger_load(&tx, &http_client, &mut app);
+    }
 
-    let tick_rate = Duration::from_millis(200);
     let mut last_tick = Instant::now();
+    let mut window_title = String::new();
 
-    loop {
+    'main: loop {
+        let tick_rate = app.settings.tick_rate();
         while let Ok(event) = rx.try_recv() {
+            if event.is_network_response() {
+                app.finish_request();
+            }
             match event {
+                #[cfg(unix)]
+                AppEvent::Hangup => {
+                    flush_state(&app);
+                    break 'main;
+                }
                 AppEvent::StationsLoaded {
                     request_id,
                     stations,
+                    fetched,
                 } => {
                     if app.is_latest_station_request(request_id) {
-                        app.set_stations(stations);
+                        if app.params.is_default_query()
+                            && !stations.is_empty()
+                            && let Err(err) = cache::save_cache(&stations)
+                        {
+                            app.set_status(err);
+                        }
+                        app.set_search_results(stations, fetched);
                     }
                 }
                 AppEvent::LoadError { request_id, err } => {
                     if app.is_latest_station_request(request_id) {
-                        app.set_error(err);
+                        match cache::load_cache() {
+                            Some(cached)
+                                if app.stations.is_empty() && app.params.is_default_query() =>
+                            {
+                                let fetched = cached.stations.len();
+                                let age = cached.age_label();
+                                app.set_search_results(cached.stations, fetched);
+                                app.set_status(format!("Showing cached results from {}", age));
+                            }
+                            _ => app.set_error(err),
+                        }
+                        trigger_resolve_servers(&http_client, &mut app);
                     }
                 }
-                AppEvent::FavoritesLoaded(mut stations, failed_uuids) => {
+                AppEvent::FavoritesLoaded(responses, failed_uuids) => {
+                    if app.remap_favorite_uuids(&responses) > 0
+                        && let Err(err) = favorites::save_favorites(
+                            &app.favorites,
+                            app.settings.favorites_tie_break,
+                        )
+                    {
+                        app.set_error(err);
+                    }
+                    let mut stations: Vec<api::Station> =
+                        responses.into_iter().map(|(_, station)| station).collect();
+                    app.pending_favorite_updates =
+                        favorites::changed_upstream(&app.favorites, &stations);
+                    if !app.pending_favorite_updates.is_empty() {
+                        app.set_status(format!(
+                            "{} favorites changed upstream. Press u to update them.",
+                            app.pending_favorite_updates.len()
+                        ));
+                    }
                     let mut seen: HashSet<String> =
                         stations.iter().map(|s| s.stationuuid.clone()).collect();
-                    for fallback in fallback_stations_from_cached(&app.favorites, &failed_uuids) {
+                    for fallback in
+                        favorites::fallback_stations_from_cached(&app.favorites, &failed_uuids)
+                    {
                         if seen.insert(fallback.stationuuid.clone()) {
                             stations.push(fallback);
                         }
                     }
                     stations.sort_by_cached_key(|s| s.name.to_lowercase());
-                    app.set_favorite_stations(stations);
+                    app.reconcile_favorite_stations(stations);
                     if !failed_uuids.is_empty() {
                         app.set_favorites_error(format!(
                             "Some favorites could not be refreshed from API ({}). Showing cached entries.",
@@ -112,27 +330,127 @@ async fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Resul
                         ));
                     }
                 }
+                AppEvent::MostPlayedLoaded(stations, failed_uuids) => {
+                    app.set_most_played_stations(stations);
+                    if !failed_uuids.is_empty() {
+                        app.set_status(format!(
+                            "{} played stations could not be loaded from the API",
+                            failed_uuids.len()
+                        ));
+                    }
+                }
+                AppEvent::SessionStationLoaded(Some(station)) => {
+                    start_station(&tx, &http_client, &mut app, &mut player, station);
+                }
+                AppEvent::SessionStationLoaded(None) => {
+                    app.set_status("Station could not be found".to_string());
+                }
+                AppEvent::Voted { name, result } => match result {
+                    Ok(()) => app.set_status(format!("Voted for {}", name)),
+                    Err(err) => app.set_status(err),
+                },
+                AppEvent::StreamChecked { station, result } => {
+                    let still_wanted = app
+                        .checking_station
+                        .as_ref()
+                        .is_some_and(|checking| checking.stationuuid == station.stationuuid);
+                    if still_wanted {
+                        app.checking_station = None;
+                        match result {
+                            Ok(url) => {
+                                app.working_urls
+                                    .insert(station.stationuuid.clone(), url.clone());
+                                play_station_url(&mut app, &mut player, station, &url);
+                                record_current_play(&mut app);
+                            }
+                            Err(err) => app.playback_error = Some(err),
+                        }
+                    }
+                }
+                #[cfg(feature = "favicons")]
+                AppEvent::FaviconLoaded { stationuuid, bytes } => {
+                    if let Some(renderer) = &favicon_renderer
+                        && let Ok(protocol) = renderer.protocol(&bytes)
+                    {
+                        app.favicon = Some((stationuuid, protocol));
+                    }
+                }
             }
         }
 
+        app.paused = player.is_paused();
+        if !app.mini {
+            let rows = terminal
+                .size()
+                .map(|s| s.height as usize)
+                .unwrap_or(20)
+                .saturating_sub(15);
+            app.keep_selection_visible(rows.max(1));
+        }
         terminal
             .draw(|f| ui::draw(f, &app, &mut table_state))
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| terminal_lost(&app, e))?;
+
+        if app.settings.terminal_title {
+            let title = app.window_title(Instant::now());
+            if title != window_title {
+                if window_title.is_empty() {
+                    let _ = execute!(terminal.backend_mut(), Print(PUSH_WINDOW_TITLE));
+                }
+                let _ = execute!(terminal.backend_mut(), SetTitle(&title));
+                window_title = title;
+            }
+        }
 
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
             .unwrap_or_default();
 
-        if event::poll(timeout).map_err(|e| e.to_string())?
-            && let Event::Key(key) = event::read().map_err(|e| e.to_string())?
-        {
+        if event::poll(timeout).map_err(|e| terminal_lost(&app, e))? {
+            let key = match event::read().map_err(|e| terminal_lost(&app, e))? {
+                Event::Key(key) => key,
+                Event::Paste(text) => {
+                    let text = text.trim();
+                    if app.mode == AppMode::Normal && text.starts_with("cradio://") {
+                        import_session(&tx, &http_client, &mut app, text);
+                    } else if app.mode == AppMode::Normal
+                        && (text.starts_with("http://") || text.starts_with("https://"))
+                    {
+                        open_stream_url(&tx, &http_client, &mut app, &mut player, text);
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
             if key.kind != KeyEventKind::Press {
                 continue;
             }
+            if app.whats_new.is_some() {
+                app.whats_new = None;
+                continue;
+            }
+
+            let normal_key = if key.modifiers.contains(KeyModifiers::CONTROL) {
+                key.code
+            } else {
+                app.keymap.translate(key.code)
+            };
+
+            if app.mode == AppMode::Normal && !app.allow_navigation(normal_key, Instant::now()) {
+                continue;
+            }
 
             match &app.mode {
-                AppMode::Normal => match key.code {
-                    KeyCode::Char('q') | KeyCode::Char('Q') => break,
+                AppMode::Normal => match normal_key {
+                    KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        reload_settings(&mut app);
+                    }
+                    KeyCode::Char('q') | KeyCode::Char('Q') => {
+                        let quit = app.request_quit();
+                        if quit {
+                            break;
+                        }
+                    }
                     KeyCode::Down => {
                         let visible = terminal
                             .size()
@@ -142,79 +460,216 @@ async fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Resul
                         app.select_next(visible.max(5));
                     }
                     KeyCode::Up => app.select_prev(),
-                    KeyCode::Enter => {
-                        if let Some(station) = app.selected_station().cloned() {
-                            let url = if !station.url_resolved.is_empty() {
-                                station.url_resolved.clone()
-                            } else {
-                                station.url.clone()
-                            };
-                            if let Some(err) = player.play(&url) {
-                                app.current_station = None;
-                                app.playback_error = Some(err);
-                            } else {
-                                app.current_station = Some(station);
-                                app.playback_error = None;
-                            }
+                    KeyCode::Left | KeyCode::Right if app.current_station.is_some() => {
+                        let seconds = if normal_key == KeyCode::Left { -10 } else { 10 };
+                        if !player.seek(seconds) {
+                            app.set_status(SEEK_UNSUPPORTED.to_string());
                         }
                     }
-                    KeyCode::Char('s') => {
-                        player.stop();
-                        app.current_station = None;
-                        app.playback_error = None;
+                    KeyCode::Enter => play_selected(&tx, &http_client, &mut app, &mut player),
+                    KeyCode::Char('w') => toggle_recording(&mut app, &mut player),
+                    KeyCode::Char('c') => {
+                        let toggled = player.toggle_pause();
+                        if !toggled {
+                            app.set_status("Nothing is playing".to_string());
+                        }
+                    }
+                    KeyCode::Char('s') => stop_playback(&mut app, &mut player),
+                    KeyCode::Char('R') => {
+                        if let Some(station) = app.reconnect_station() {
+                            start_station(&tx, &http_client, &mut app, &mut player, station);
+                        }
+                    }
+                    KeyCode::Char('F') => {
+                        if let Some(station) = app.forget_current_station() {
+                            app.set_status(format!(
+                                "Forgot {}; audio keeps playing until stopped",
+                                station.name
+                            ));
+                        }
                     }
                     KeyCode::Char('/') => {
                         app.mode = AppMode::Filtering(InputField::Name);
                     }
+                    KeyCode::Char('&') => app.start_quick_filter(),
+                    KeyCode::Char(':') => {
+                        app.command_input.clear();
+                        app.mode = AppMode::Command;
+                    }
+                    KeyCode::Char('?') => {
+                        app.mode = AppMode::Help;
+                    }
                     KeyCode::Char(' ') => {
-                        if app.toggle_favorite_for_selected().is_some() {
-                            if let Err(err) = favorites::save_favorites(&app.favorites) {
-                                app.set_error(err);
-                            } else {
-                                app.error = None;
+                        let selected = app.selected;
+                        toggle_favorite(&mut app, selected);
+                    }
+                    KeyCode::Char(c @ '1'..='9') => {
+                        let row = c.to_digit(10).unwrap_or(1) as usize - 1;
+                        if let Some(index) = app.visible_row_index(table_state.offset(), row) {
+                            let name = app.current_station_list()[index].name.clone();
+                            match toggle_favorite(&mut app, index) {
+                                Some(true) => {
+                                    app.set_status(format!("Added {} to favorites", name))
+                                }
+                                Some(false) => {
+                                    app.set_status(format!("Removed {} from favorites", name))
+                                }
+                                None => {}
                             }
                         }
                     }
                     KeyCode::Char('f') => {
                         if app.view_mode == StationViewMode::AllStations {
-                            app.set_view_mode(StationViewMode::Favorites);
-                            app.favorites_error = None;
-                            if app.favorites.is_empty() {
-                                app.set_favorite_stations(Vec::new());
-                            } else {
-                                app.favorites_loading = true;
-                                let uuids: Vec<String> = app
-                                    .favorites
-                                    .iter()
-                                    .map(|f| f.stationuuid.clone())
-                                    .collect();
-                                trigger_load_favorites(&tx, &http_client, uuids);
-                            }
+                            refresh_favorites(&tx, &http_client, &mut app);
                         } else {
                             app.set_view_mode(StationViewMode::AllStations);
                         }
                     }
-                    KeyCode::Char('n') => {
-                        if !app.loading && app.view_mode == StationViewMode::AllStations {
-                            if app.next_page() {
-                                trigger_load(&tx, &http_client, &mut app);
+                    KeyCode::Char('M') => {
+                        if app.view_mode == StationViewMode::MostPlayed {
+                            app.set_view_mode(StationViewMode::AllStations);
+                        } else if let Some(uuids) = app.show_most_played() {
+                            trigger_load_most_played(&tx, &http_client, &mut app, uuids);
+                        }
+                    }
+                    KeyCode::Char('h') => {
+                        let mode = if app.view_mode == StationViewMode::History {
+                            StationViewMode::AllStations
+                        } else {
+                            StationViewMode::History
+                        };
+                        app.set_view_mode(mode);
+                    }
+                    KeyCode::Char('l') => refresh_favorites(&tx, &http_client, &mut app),
+                    KeyCode::Char('L') => app.show_cached_favorites(),
+                    KeyCode::Char('u') if !app.pending_favorite_updates.is_empty() => {
+                        let updated = app.apply_favorite_updates();
+                        match favorites::save_favorites(
+                            &app.favorites,
+                            app.settings.favorites_tie_break,
+                        ) {
+                            Ok(()) => app.set_status(format!("Updated {} favorites", updated)),
+                            Err(err) => app.set_error(err),
+                        }
+                    }
+                    KeyCode::Char('i') => {
+                        app.show_details = !app.show_details;
+                        #[cfg(feature = "favicons")]
+                        if app.show_details && favicon_renderer.is_some() {
+                            trigger_load_favicon(&tx, &http_client, &mut app);
+                        }
+                    }
+                    KeyCode::Esc => {
+                        app.show_details = false;
+                    }
+                    KeyCode::Char('e') => {
+                        if let Some(station) = app.current_station.clone() {
+                            let url = app.playback_url(&station);
+                            match external::open_in_external_player(
+                                &url,
+                                app.settings.external_player.as_deref(),
+                            ) {
+                                Ok(()) => {
+                                    if !app.settings.external_keep_playing {
+                                        stop_playback(&mut app, &mut player);
+                                    }
+                                    app.set_status(format!(
+                                        "Opened {} in external player",
+                                        station.name
+                                    ));
+                                }
+                                Err(err) => app.set_status(err),
                             }
                         }
                     }
-                    KeyCode::Char('p') => {
-                        if !app.loading && app.view_mode == StationViewMode::AllStations {
-                            if app.prev_page() {
+                    KeyCode::Char('S') if app.view_mode == StationViewMode::Favorites => {
+                        match app.find_similar_to_selected() {
+                            Ok(name) => {
+                                app.set_status(format!("Searching for stations like {}", name));
                                 trigger_load(&tx, &http_client, &mut app);
                             }
+                            Err(err) => app.set_status(err),
+                        }
+                    }
+                    KeyCode::Char('y') => match app.now_playing_text() {
+                        Some(text) => match clipboard::copy_to_clipboard(&text) {
+                            Ok(()) => app.set_status(format!("Copied: {}", text)),
+                            Err(err) => app.set_status(err),
+                        },
+                        None => app.set_status("Nothing is playing".to_string()),
+                    },
+                    KeyCode::Char('x') => {
+                        let link = app.session_snapshot().to_url();
+                        match clipboard::copy_to_clipboard(&link) {
+                            Ok(()) => app.set_status(format!("Session link copied: {}", link)),
+                            Err(err) => app.set_status(err),
+                        }
+                    }
+                    KeyCode::Char('n')
+                        if !app.loading && app.view_mode == StationViewMode::AllStations =>
+                    {
+                        let paged = app.next_page();
+                        if paged {
+                            trigger_load(&tx, &http_client, &mut app);
+                        }
+                    }
+                    KeyCode::Char('p')
+                        if !app.loading && app.view_mode == StationViewMode::AllStations =>
+                    {
+                        let paged = app.prev_page();
+                        if paged {
+                            trigger_load(&tx, &http_client, &mut app);
+                        }
+                    }
+                    KeyCode::Char('r')
+                        if !app.loading && app.view_mode == StationViewMode::AllStations =>
+                    {
+                        app.toggle_reverse();
+                        trigger_load(&tx, &http_client, &mut app);
+                    }
+                    KeyCode::Char('g')
+                        if !app.loading && app.view_mode == StationViewMode::AllStations =>
+                    {
+                        app.cycle_codec();
+                        trigger_load(&tx, &http_client, &mut app);
+                    }
+                    KeyCode::Char('v') => trigger_vote(&tx, &http_client, &mut app),
+                    KeyCode::Char('o') if app.view_mode == StationViewMode::AllStations => {
+                        app.cycle_sort();
+                    }
+                    KeyCode::Char('m') => {
+                        app.toggle_mini();
+                        save_preferences(&mut app);
+                    }
+                    KeyCode::Char('#') => {
+                        app.settings.show_range = !app.settings.show_range;
+                        save_preferences(&mut app);
+                    }
+                    KeyCode::Char('U') => {
+                        let message = if app.toggle_prefer_resolved() {
+                            "Playback prefers resolved stream URLs"
+                        } else {
+                            "Playback prefers original stream URLs"
+                        };
+                        app.set_status(message.to_string());
+                        save_preferences(&mut app);
+                    }
+                    KeyCode::F(n) => {
+                        let preset = (n as usize)
+                            .checked_sub(1)
+                            .and_then(|index| app.settings.volume_presets.get(index));
+                        if let Some(&preset) = preset {
+                            player.set_volume(preset);
+                            remember_volume(&mut app, &player);
                         }
                     }
                     KeyCode::Char('+') => {
                         player.volume_up();
-                        app.volume = player.volume;
+                        remember_volume(&mut app, &player);
                     }
                     KeyCode::Char('-') => {
                         player.volume_down();
-                        app.volume = player.volume;
+                        remember_volume(&mut app, &player);
                     }
                     _ => {}
                 },
@@ -238,85 +693,725 @@ async fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Resul
                         }
                     }
                     KeyCode::Char(c) => {
-                        let bitrate_only =
-                            matches!(app.mode, AppMode::Filtering(InputField::Bitrate));
-                        if (!bitrate_only || c.is_ascii_digit())
-                            && let Some(field) = app.active_field_mut()
-                        {
-                            field.push(c);
+                        let accepted = app.push_filter_char(c);
+                        if !accepted {
+                            app.set_status(format!(
+                                "Filters are limited to {} characters",
+                                app::MAX_FILTER_LEN
+                            ));
+                        }
+                    }
+                    _ => {}
+                },
+                AppMode::QuickFilter(_) => match key.code {
+                    KeyCode::Esc => app.cancel_quick_filter(),
+                    KeyCode::Enter => {
+                        let accepted = app.accept_quick_filter();
+                        if accepted {
+                            play_selected(&tx, &http_client, &mut app, &mut player);
+                        }
+                    }
+                    KeyCode::Down => app.select_next(0),
+                    KeyCode::Up => app.select_prev(),
+                    KeyCode::Backspace => app.pop_quick_filter_char(),
+                    KeyCode::Char(c) => app.push_quick_filter_char(c),
+                    _ => {}
+                },
+                AppMode::Help => {
+                    if key.code == KeyCode::Esc || normal_key == KeyCode::Char('?') {
+                        app.mode = AppMode::Normal;
+                    }
+                }
+                AppMode::ConfirmQuit => match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => break,
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        app.mode = AppMode::Normal;
+                    }
+                    _ => {}
+                },
+                AppMode::Command => match key.code {
+                    KeyCode::Esc => {
+                        app.mode = AppMode::Normal;
+                    }
+                    KeyCode::Enter => {
+                        app.mode = AppMode::Normal;
+                        let line = std::mem::take(&mut app.command_input);
+                        match command::parse_command(&line) {
+                            Ok(command) => {
+                                run_command(&tx, &http_client, &mut app, &mut player, command)
+                            }
+                            Err(err) => app.set_status(err),
                         }
                     }
+                    KeyCode::Backspace => {
+                        let removed = app.command_input.pop();
+                        if removed.is_none() {
+                            app.mode = AppMode::Normal;
+                        }
+                    }
+                    KeyCode::Char(c) => app.command_input.push(c),
                     _ => {}
                 },
             }
         }
 
         if last_tick.elapsed() >= tick_rate {
+            if app.current_station.is_some()
+                && (player.has_exited() || player.state() == PlaybackState::Error)
+            {
+                let unavailable = player.state() == PlaybackState::Error;
+                player.stop();
+                match app.alternate_after_early_failure(Instant::now()) {
+                    Some((station, url)) => {
+                        app.set_status(format!(
+                            "Retrying {} with its other stream URL",
+                            station.name
+                        ));
+                        play_station_url(&mut app, &mut player, station, &url);
+                        app.retried_alternate = true;
+                    }
+                    None if unavailable => app.note_stream_unavailable(),
+                    None => app.note_stream_dropped(),
+                }
+            }
+            app.remember_working_url(Instant::now());
+            if app.recording.is_some() && !player.is_recording() {
+                app.recording = None;
+            }
+            player.poll_metadata();
+            app.playback_state = player.state();
+            let track = player.now_playing_title();
+            if track != app.current_track {
+                if app.settings.notifications
+                    && let (Some(station), Some(track)) = (&app.current_station, &track)
+                {
+                    notification::notify_now_playing(&station.name, Some(track));
+                }
+                app.current_track = track;
+            }
+            if player.take_seek_failure() {
+                app.set_status(SEEK_UNSUPPORTED.to_string());
+            }
+            if let Some(warning) = player.check_rc_interface() {
+                app.set_status(warning);
+            }
+            app.tick();
             last_tick = Instant::now();
         }
     }
 
+    if !window_title.is_empty() {
+        let _ = execute!(terminal.backend_mut(), Print(POP_WINDOW_TITLE));
+    }
+    app.abort_tasks();
     Ok(())
 }
 
-fn fallback_stations_from_cached(
-    favorites: &[FavoriteEntry],
-    failed_uuids: &[String],
-) -> Vec<api::Station> {
-    let failed_set: HashSet<&str> = failed_uuids.iter().map(String::as_str).collect();
+fn terminal_lost(app: &App, err: std::io::Error) -> String {
+    flush_state(app);
+    err.to_string()
+}
 
-    favorites
-        .iter()
-        .filter(|fav| failed_set.contains(fav.stationuuid.as_str()))
-        .map(|fav| api::Station {
-            stationuuid: fav.stationuuid.clone(),
-            name: fav.name.clone(),
-            url: fav.url.clone(),
-            url_resolved: String::new(),
-            tags: String::new(),
-            country_code: String::new(),
-            language: String::new(),
-            bitrate: 0,
-        })
-        .collect()
+fn flush_state(app: &App) {
+    if app.favorites_loaded {
+        let _ = favorites::save_favorites(&app.favorites, app.settings.favorites_tie_break);
+    }
+    let _ = save_settings(app);
+}
+
+fn note_version_seen(app: &mut App) {
+    let last_seen = app.settings.last_seen_version.as_deref();
+    if last_seen == Some(release_notes::CURRENT_VERSION) {
+        return;
+    }
+
+    app.whats_new = release_notes::whats_new(last_seen);
+    app.settings.last_seen_version = Some(release_notes::CURRENT_VERSION.to_string());
+    if let Err(err) = save_settings(app) {
+        app.set_error(err);
+    }
+}
+
+fn save_settings(app: &App) -> Result<(), String> {
+    if !app.settings_loaded {
+        return Err("Settings not saved: the settings file could not be loaded".to_string());
+    }
+    settings::save_settings(&app.settings)
+}
+
+fn save_preferences(app: &mut App) {
+    if let Err(err) = save_settings(app) {
+        app.set_status(err);
+    }
+}
+
+fn reload_settings(app: &mut App) {
+    match settings::load_settings() {
+        Ok(loaded) => match app.apply_settings(loaded) {
+            Ok(()) => {
+                app.settings_loaded = true;
+                app.set_status("Settings reloaded".to_string());
+            }
+            Err(err) => app.set_status(err),
+        },
+        Err(err) => app.set_status(err),
+    }
+}
+
+fn play_selected(
+    tx: &mpsc::UnboundedSender<AppEvent>,
+    client: &reqwest::Client,
+    app: &mut App,
+    player: &mut Player,
+) {
+    let Some(station) = app.selected_station().cloned() else {
+        return;
+    };
+    let is_current = app
+        .current_station
+        .as_ref()
+        .is_some_and(|current| current.stationuuid == station.stationuuid);
+    if !(is_current && player.is_paused() && player.resume()) {
+        register_click(client, app, &station);
+        app.push_history(&station);
+        start_station(tx, client, app, player, station);
+    }
+}
+
+fn start_station(
+    tx: &mpsc::UnboundedSender<AppEvent>,
+    client: &reqwest::Client,
+    app: &mut App,
+    player: &mut Player,
+    station: api::Station,
+) {
+    if !app.settings.check_reachability {
+        app.checking_station = None;
+        play_station(app, player, station);
+        record_current_play(app);
+        return;
+    }
+
+    app.checking_station = Some(station.clone());
+    app.playback_error = None;
+    let candidates = match app.url_override(&station.stationuuid) {
+        Some(url) => vec![url.to_string()],
+        None => {
+            let mut candidates = api::stream_candidates(&station, app.settings.prefer_resolved);
+            if let Some(known) = app.working_urls.get(&station.stationuuid) {
+                candidates.retain(|url| url != known);
+                candidates.insert(0, known.clone());
+            }
+            candidates
+        }
+    };
+    let tx = tx.clone();
+    let client = client.clone();
+    app.begin_request();
+    app.track_task(tokio::spawn(async move {
+        let result = api::check_stream(&client, &candidates).await;
+        let _ = tx.send(AppEvent::StreamChecked { station, result });
+    }));
+}
+
+fn register_click(client: &reqwest::Client, app: &mut App, station: &api::Station) {
+    if station.stationuuid.starts_with("url:") {
+        return;
+    }
+    let client = client.clone();
+    let server = app.servers.current();
+    let station_uuid = station.stationuuid.clone();
+    app.track_task(tokio::spawn(async move {
+        api::register_click(&client, &server, &station_uuid).await;
+    }));
+}
+
+fn play_station(app: &mut App, player: &mut Player, station: api::Station) {
+    let url = app.playback_url(&station);
+    play_station_url(app, player, station, &url);
+}
+
+fn play_station_url(app: &mut App, player: &mut Player, station: api::Station, url: &str) {
+    #[cfg(not(target_os = "windows"))]
+    {
+        player.audio_device = app.settings.audio_device.clone();
+        player.quit_grace = app.settings.quit_grace();
+        player.output = app
+            .settings
+            .stream_output
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(String::from);
+    }
+    let result = player.play(url);
+    app.playback_state = player.state();
+    if let Some(err) = result {
+        app.current_station = None;
+        app.playback_error = Some(err);
+    } else {
+        if app.settings.notifications {
+            notification::notify_now_playing(&station.name, None);
+        }
+        #[cfg(not(target_os = "windows"))]
+        if let Some(path) = &player.output {
+            app.set_status(format!("Writing {} to {}", station.name, path));
+        }
+        app.current_station = Some(station);
+        app.playing_since = Some(Instant::now());
+        app.playing_url = Some(url.to_string());
+        app.retried_alternate = false;
+        app.current_track = None;
+        app.last_station = None;
+        app.playback_error = None;
+    }
+}
+
+fn record_current_play(app: &mut App) {
+    let Some(uuid) = app
+        .current_station
+        .as_ref()
+        .map(|station| station.stationuuid.clone())
+    else {
+        return;
+    };
+    app.record_play(&uuid);
+    if app.stats_loaded
+        && let Err(err) = stats::save_stats(&app.stats)
+    {
+        app.set_status(err);
+    }
+}
+
+fn remember_volume(app: &mut App, player: &Player) {
+    if app.volume == player.volume {
+        return;
+    }
+    app.volume = player.volume;
+    if let Err(err) = state::save_state(&state::State { volume: app.volume }) {
+        app.set_status(err);
+    }
+}
+
+fn stop_playback(app: &mut App, player: &mut Player) {
+    player.stop();
+    app.recording = None;
+    app.checking_station = None;
+    app.current_station = None;
+    app.playback_error = None;
+}
+
+fn toggle_recording(app: &mut App, player: &mut Player) {
+    if let Some(path) = player.stop_recording() {
+        app.recording = None;
+        app.set_status(format!("Recording saved to {}", path.display()));
+        return;
+    }
+    let (Some(station), Some(url)) = (&app.current_station, &app.playing_url) else {
+        app.set_status("Nothing is playing".to_string());
+        return;
+    };
+    let result = recording::new_recording_path(
+        app.settings.recordings_dir.as_deref(),
+        &station.name,
+        &station.codec,
+    )
+    .and_then(|path| player.start_recording(url, &path).map(|()| path));
+    match result {
+        Ok(path) => {
+            app.set_status(format!("Recording to {}", path.display()));
+            app.recording = Some(path);
+        }
+        Err(err) => app.set_status(err),
+    }
+}
+
+fn toggle_favorite(app: &mut App, index: usize) -> Option<bool> {
+    if let Err(warning) = app.check_favorite_limit(index) {
+        app.set_status(warning);
+        return None;
+    }
+    let now_favorite = app.toggle_favorite_at(index)?;
+    if let Err(err) = favorites::save_favorites(&app.favorites, app.settings.favorites_tie_break) {
+        app.set_error(err);
+    } else {
+        app.error = None;
+    }
+    Some(now_favorite)
+}
+
+fn run_command(
+    tx: &mpsc::UnboundedSender<AppEvent>,
+    client: &reqwest::Client,
+    app: &mut App,
+    player: &mut Player,
+    command: Command,
+) {
+    match command {
+        Command::Play(uuid) => {
+            let known = app
+                .stations
+                .iter()
+                .chain(app.favorite_stations.iter())
+                .find(|station| station.stationuuid == uuid)
+                .cloned();
+            match known {
+                Some(station) => start_station(tx, client, app, player, station),
+                None => trigger_load_session_station(tx, client, app, uuid),
+            }
+        }
+        Command::Search(query) => {
+            app.draft_name = query;
+            app.update_params_from_drafts();
+            app.set_view_mode(StationViewMode::AllStations);
+            app.loading = true;
+            trigger_load(tx, client, app);
+        }
+        Command::Saved(name) => match app.apply_saved_search(&name) {
+            Ok(()) => {
+                app.set_view_mode(StationViewMode::AllStations);
+                app.loading = true;
+                trigger_load(tx, client, app);
+            }
+            Err(err) => app.set_status(err),
+        },
+        Command::Goto(page) => {
+            if !app.go_to_page(page) {
+                app.set_status("Page out of range".to_string());
+                return;
+            }
+            app.set_view_mode(StationViewMode::AllStations);
+            app.loading = true;
+            trigger_load(tx, client, app);
+        }
+        Command::Theme(name) => match name.as_str() {
+            "mono" | "monochrome" => {
+                app.set_theme(Theme::Mono);
+                save_preferences(app);
+            }
+            "default" | "color" => {
+                app.set_theme(Theme::Default);
+                save_preferences(app);
+            }
+            _ => app.set_status(format!("Unknown theme: {}", name)),
+        },
+        Command::Export { format, path } => {
+            let extension = match format.as_str() {
+                "m3u" => "m3u",
+                "txt" | "txt+names" => "txt",
+                _ => {
+                    app.set_status(format!("Unsupported export format: {}", format));
+                    return;
+                }
+            };
+            let path = path.unwrap_or_else(|| format!("cradio-favorites.{}", extension));
+            let target = std::path::Path::new(&path);
+            let result = if extension == "m3u" {
+                favorites::export_m3u(&app.favorites, target)
+            } else {
+                favorites::export_txt(
+                    &app.favorites,
+                    target,
+                    format == "txt+names",
+                    app.settings.export_separator,
+                )
+            };
+            match result {
+                Ok(()) => app.set_status(format!(
+                    "Exported {} favorites to {}",
+                    app.favorites.len(),
+                    path
+                )),
+                Err(err) => app.set_status(err),
+            }
+        }
+        Command::Import(path) => import_favorites(app, &paths::expand_home(&path)),
+        Command::Open(url) => open_stream_url(tx, client, app, player, &url),
+        Command::Device(None) => match audio::list_output_devices() {
+            Ok(devices) if devices.is_empty() => {
+                app.set_status("No audio devices found".to_string());
+            }
+            Ok(devices) => app.set_status(format!("Audio devices: {}", devices.join(", "))),
+            Err(err) => app.set_status(err),
+        },
+        Command::Device(Some(device)) => {
+            app.settings.audio_device = (device != "default").then_some(device);
+            if let Err(err) = save_settings(app) {
+                app.set_status(err);
+            } else {
+                app.set_status(format!(
+                    "Audio device: {}",
+                    app.settings.audio_device.as_deref().unwrap_or("default")
+                ));
+            }
+            if let Some(station) = app.current_station.clone() {
+                start_station(tx, client, app, player, station);
+            }
+        }
+        Command::Url(url) => {
+            let clearing = url.is_none();
+            match app.set_url_override_for_selected(url) {
+                Ok(name) => {
+                    if let Err(err) =
+                        favorites::save_favorites(&app.favorites, app.settings.favorites_tie_break)
+                    {
+                        app.set_error(err);
+                    } else if clearing {
+                        app.set_status(format!("Cleared custom URL for {}", name));
+                    } else {
+                        app.set_status(format!("Pinned custom URL for {}", name));
+                    }
+                }
+                Err(err) => app.set_status(err),
+            }
+        }
+    }
+}
+
+fn import_favorites(app: &mut App, path: &std::path::Path) {
+    if !app.favorites_loaded {
+        app.set_status("Import skipped: the favorites file could not be loaded".to_string());
+        return;
+    }
+    let imported = match favorites::import_playlist(path) {
+        Ok(imported) => imported,
+        Err(err) => {
+            app.set_status(err);
+            return;
+        }
+    };
+    let found = imported.len();
+    let mut merged = favorites::merge_favorites(&app.favorites, imported);
+    let skipped = app.cap_imported_favorites(&mut merged);
+    let added = merged.len() - app.favorites.len();
+    if let Err(err) = favorites::save_favorites(&merged, app.settings.favorites_tie_break) {
+        app.set_status(err);
+        return;
+    }
+    app.set_favorites(merged);
+    if app.view_mode == StationViewMode::Favorites {
+        app.show_cached_favorites();
+    }
+    let mut status = format!(
+        "Imported {} of {} stations from {}",
+        added,
+        found,
+        path.display()
+    );
+    if skipped > 0 {
+        status.push_str(&format!(
+            "; {} skipped, favorites limit ({}) reached",
+            skipped,
+            app.settings.max_favorites.unwrap_or_default()
+        ));
+    }
+    if let Some(warning) = app.favorites_limit_warning() {
+        status = format!("{}. {}", status, warning);
+    }
+    app.set_status(status);
+}
+
+fn open_stream_url(
+    tx: &mpsc::UnboundedSender<AppEvent>,
+    client: &reqwest::Client,
+    app: &mut App,
+    player: &mut Player,
+    url: &str,
+) {
+    match api::adhoc_station(url) {
+        Ok(station) => start_station(tx, client, app, player, station),
+        Err(err) => app.set_status(err),
+    }
+}
+
+fn refresh_favorites(
+    tx: &mpsc::UnboundedSender<AppEvent>,
+    client: &reqwest::Client,
+    app: &mut App,
+) {
+    if let Some(uuids) = app.refresh_favorites() {
+        trigger_load_favorites(tx, client, app, uuids);
+    }
+}
+
+fn import_session(
+    tx: &mpsc::UnboundedSender<AppEvent>,
+    client: &reqwest::Client,
+    app: &mut App,
+    link: &str,
+) {
+    let snapshot = match SessionSnapshot::from_url(link) {
+        Ok(snapshot) => snapshot,
+        Err(err) => {
+            app.set_status(err);
+            return;
+        }
+    };
+
+    app.apply_session(&snapshot);
+    app.loading = true;
+    trigger_load(tx, client, app);
+    if app.view_mode == StationViewMode::Favorites {
+        refresh_favorites(tx, client, app);
+    }
+    if let Some(uuid) = snapshot.playing_uuid {
+        trigger_load_session_station(tx, client, app, uuid);
+    }
+    app.set_status("Session imported".to_string());
 }
 
 fn trigger_load(tx: &mpsc::UnboundedSender<AppEvent>, client: &reqwest::Client, app: &mut App) {
     let tx = tx.clone();
     let client = client.clone();
+    let source = app.station_source();
     let params = app.params.clone();
     let request_id = app.note_station_request();
-    tokio::spawn(async move {
-        match api::search_stations(&client, &params).await {
-            Ok(stations) => {
+    app.begin_request();
+    app.track_task(tokio::spawn(async move {
+        match source.search(&client, &params).await {
+            Ok((stations, fetched)) => {
                 let _ = tx.send(AppEvent::StationsLoaded {
                     request_id,
                     stations,
+                    fetched,
                 });
             }
             Err(e) => {
-                let _ = tx.send(AppEvent::LoadError { request_id, err: e });
+                let _ = tx.send(AppEvent::LoadError {
+                    request_id,
+                    err: e.to_string(),
+                });
             }
         }
-    });
+    }));
 }
 
 fn trigger_load_favorites(
     tx: &mpsc::UnboundedSender<AppEvent>,
     client: &reqwest::Client,
+    app: &mut App,
     uuids: Vec<String>,
 ) {
     let tx = tx.clone();
     let client = client.clone();
-    tokio::spawn(async move {
-        let (stations, failed_uuids) = api::fetch_stations_by_uuids(&client, uuids).await;
+    let servers = app.servers.clone();
+    app.begin_request();
+    app.track_task(tokio::spawn(async move {
+        let (stations, failed_uuids) = api::fetch_stations_by_uuids(&client, &servers, uuids).await;
         let _ = tx.send(AppEvent::FavoritesLoaded(stations, failed_uuids));
-    });
+    }));
+}
+
+fn trigger_vote(tx: &mpsc::UnboundedSender<AppEvent>, client: &reqwest::Client, app: &mut App) {
+    let Some(station) = app.selected_station().cloned() else {
+        app.set_status("No station selected".to_string());
+        return;
+    };
+    if station.stationuuid.starts_with("url:") {
+        app.set_status(format!("{} is not in the station directory", station.name));
+        return;
+    }
+    let tx = tx.clone();
+    let client = client.clone();
+    let server = app.servers.current();
+    app.begin_request();
+    app.track_task(tokio::spawn(async move {
+        let result = api::vote_station(&client, &server, &station.stationuuid).await;
+        let _ = tx.send(AppEvent::Voted {
+            name: station.name,
+            result,
+        });
+    }));
+}
+
+fn trigger_load_most_played(
+    tx: &mpsc::UnboundedSender<AppEvent>,
+    client: &reqwest::Client,
+    app: &mut App,
+    uuids: Vec<String>,
+) {
+    let tx = tx.clone();
+    let client = client.clone();
+    let servers = app.servers.clone();
+    app.begin_request();
+    app.track_task(tokio::spawn(async move {
+        let (stations, failed_uuids) = api::fetch_stations_by_uuids(&client, &servers, uuids).await;
+        let _ = tx.send(AppEvent::MostPlayedLoaded(stations, failed_uuids));
+    }));
+}
+
+fn trigger_load_session_station(
+    tx: &mpsc::UnboundedSender<AppEvent>,
+    client: &reqwest::Client,
+    app: &mut App,
+    uuid: String,
+) {
+    let tx = tx.clone();
+    let client = client.clone();
+    let servers = app.servers.clone();
+    app.begin_request();
+    app.track_task(tokio::spawn(async move {
+        let (stations, _) = api::fetch_stations_by_uuids(&client, &servers, vec![uuid]).await;
+        let _ = tx.send(AppEvent::SessionStationLoaded(
+            stations.into_iter().next().map(|(_, station)| station),
+        ));
+    }));
+}
+
+#[cfg(unix)]
+fn trigger_hangup_listener(tx: &mpsc::UnboundedSender<AppEvent>, app: &mut App) {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let tx = tx.clone();
+    app.track_task(tokio::spawn(async move {
+        let Ok(mut hangup) = signal(SignalKind::hangup()) else {
+            return;
+        };
+        if hangup.recv().await.is_some() {
+            let _ = tx.send(AppEvent::Hangup);
+        }
+    }));
+}
+
+fn trigger_resolve_servers(client: &reqwest::Client, app: &mut App) {
+    let client = client.clone();
+    let servers = app.servers.clone();
+    app.track_task(tokio::spawn(async move {
+        servers.replace(api::resolve_api_servers(&client).await);
+    }));
+}
+
+#[cfg(feature = "favicons")]
+fn trigger_load_favicon(
+    tx: &mpsc::UnboundedSender<AppEvent>,
+    client: &reqwest::Client,
+    app: &mut App,
+) {
+    let Some(station) = app.selected_station() else {
+        return;
+    };
+    let already_loaded = app
+        .favicon
+        .as_ref()
+        .is_some_and(|(uuid, _)| *uuid == station.stationuuid);
+    if station.favicon.is_empty() || already_loaded {
+        return;
+    }
+
+    let tx = tx.clone();
+    let client = client.clone();
+    let stationuuid = station.stationuuid.clone();
+    let url = station.favicon.clone();
+    app.track_task(tokio::spawn(async move {
+        if let Ok(bytes) = favicon::fetch_favicon(&client, &url).await {
+            let _ = tx.send(AppEvent::FaviconLoaded { stationuuid, bytes });
+        }
+    }));
 }
 
 #[cfg(test)]
 mod tests {
-    use super::AppEvent;
+    use super::{AppEvent, CliOptions, import_favorites, save_settings};
     use crate::{
         api::{SearchParams, Station},
         app::App,
@@ -332,6 +1427,10 @@ mod tests {
             country_code: String::new(),
             language: String::new(),
             bitrate: 0,
+            favicon: String::new(),
+            votes: 0,
+            codec: String::new(),
+            homepage: String::new(),
         }
     }
 
@@ -340,9 +1439,10 @@ mod tests {
             AppEvent::StationsLoaded {
                 request_id,
                 stations,
+                fetched,
             } => {
                 if app.is_latest_station_request(request_id) {
-                    app.set_stations(stations);
+                    app.set_search_results(stations, fetched);
                 }
             }
             AppEvent::LoadError { request_id, err } => {
@@ -350,10 +1450,44 @@ mod tests {
                     app.set_error(err);
                 }
             }
-            AppEvent::FavoritesLoaded(_, _) => {}
+            AppEvent::FavoritesLoaded(_, _)
+            | AppEvent::MostPlayedLoaded(_, _)
+            | AppEvent::SessionStationLoaded(_)
+            | AppEvent::StreamChecked { .. }
+            | AppEvent::Voted { .. } => {}
+            #[cfg(feature = "favicons")]
+            AppEvent::FaviconLoaded { .. } => {}
+            #[cfg(unix)]
+            AppEvent::Hangup => {}
         }
     }
 
+    #[test]
+    fn cli_options_parse_known_flags_and_reject_unknown_ones() {
+        assert_eq!(CliOptions::from_args(Vec::new()), Ok(CliOptions::default()));
+        assert_eq!(
+            CliOptions::from_args(vec!["--no-auto-search".to_string()]),
+            Ok(CliOptions {
+                no_auto_search: true,
+                no_altscreen: false,
+            })
+        );
+        assert_eq!(
+            CliOptions::from_args(vec![
+                "--no-altscreen".to_string(),
+                "--no-auto-search".to_string()
+            ]),
+            Ok(CliOptions {
+                no_auto_search: true,
+                no_altscreen: true,
+            })
+        );
+        assert_eq!(
+            CliOptions::from_args(vec!["--loud".to_string()]),
+            Err("Unknown argument: --loud".to_string())
+        );
+    }
+
     #[test]
     fn stale_station_loads_are_ignored() {
         let mut app = App::new();
@@ -366,6 +1500,7 @@ mod tests {
             AppEvent::StationsLoaded {
                 request_id: second,
                 stations: vec![station("new", "New")],
+                fetched: 1,
             },
         );
         apply_station_event(
@@ -373,10 +1508,31 @@ mod tests {
             AppEvent::StationsLoaded {
                 request_id: first,
                 stations: vec![station("old", "Old")],
+                fetched: 1,
             },
         );
 
         assert_eq!(app.stations.len(), 1);
         assert_eq!(app.stations[0].stationuuid, "new");
     }
+
+    #[test]
+    fn importing_is_refused_when_favorites_failed_to_load() {
+        let mut app = App::new();
+        import_favorites(&mut app, std::path::Path::new("/nonexistent/list.m3u"));
+        assert_eq!(
+            app.status.as_deref(),
+            Some("Import skipped: the favorites file could not be loaded")
+        );
+        assert!(app.favorites.is_empty());
+    }
+
+    #[test]
+    fn settings_are_not_saved_when_they_failed_to_load() {
+        let app = App::new();
+        assert_eq!(
+            save_settings(&app),
+            Err("Settings not saved: the settings file could not be loaded".to_string())
+        );
+    }
 }
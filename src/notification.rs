@@ -0,0 +1,38 @@
+use notify_rust::Notification;
+
+fn notification_body(station: &str, track: Option<&str>) -> String {
+    match track.map(str::trim).filter(|track| !track.is_empty()) {
+        Some(track) => format!("{}\n{}", track, station),
+        None => station.to_string(),
+    }
+}
+
+pub fn notify_now_playing(station: &str, track: Option<&str>) {
+    let body = notification_body(station, track);
+    tokio::task::spawn_blocking(move || {
+        let _ = Notification::new()
+            .appname("cradio")
+            .summary("Now playing")
+            .body(&body)
+            .show();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::notification_body;
+
+    #[test]
+    fn notification_body_uses_station_name_without_track() {
+        assert_eq!(notification_body("Jazz FM", None), "Jazz FM");
+        assert_eq!(notification_body("Jazz FM", Some("  ")), "Jazz FM");
+    }
+
+    #[test]
+    fn notification_body_puts_track_before_station() {
+        assert_eq!(
+            notification_body("Jazz FM", Some("Miles Davis - So What")),
+            "Miles Davis - So What\nJazz FM"
+        );
+    }
+}
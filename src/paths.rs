@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use directories::{BaseDirs, ProjectDirs};
+
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", "cradio")
+}
+
+pub fn config_dir() -> Result<PathBuf, String> {
+    project_dirs()
+        .map(|dirs| dirs.config_dir().to_path_buf())
+        .ok_or_else(|| "Unable to determine a config directory for this platform".to_string())
+}
+
+pub fn data_dir() -> Result<PathBuf, String> {
+    project_dirs()
+        .map(|dirs| dirs.data_dir().to_path_buf())
+        .ok_or_else(|| "Unable to determine a data directory for this platform".to_string())
+}
+
+pub fn cache_dir() -> Result<PathBuf, String> {
+    project_dirs()
+        .map(|dirs| dirs.cache_dir().to_path_buf())
+        .ok_or_else(|| "Unable to determine a cache directory for this platform".to_string())
+}
+
+pub fn expand_home(path: &str) -> PathBuf {
+    let rest = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => rest.trim_start_matches('/'),
+        _ => return PathBuf::from(path),
+    };
+    match BaseDirs::new() {
+        Some(dirs) => dirs.home_dir().join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cache_dir, config_dir, data_dir, expand_home};
+    use directories::BaseDirs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn native_directories_are_named_after_cradio() {
+        assert!(
+            config_dir()
+                .expect("config dir")
+                .to_string_lossy()
+                .contains("cradio")
+        );
+        assert!(
+            data_dir()
+                .expect("data dir")
+                .to_string_lossy()
+                .contains("cradio")
+        );
+        assert!(
+            cache_dir()
+                .expect("cache dir")
+                .to_string_lossy()
+                .contains("cradio")
+        );
+    }
+
+    #[test]
+    fn expand_home_replaces_a_leading_tilde_only() {
+        let home = BaseDirs::new().expect("home dir").home_dir().to_path_buf();
+        assert_eq!(expand_home("~/My Radio.pls"), home.join("My Radio.pls"));
+        assert_eq!(expand_home("~"), home);
+        assert_eq!(
+            expand_home("~other/list.m3u"),
+            PathBuf::from("~other/list.m3u")
+        );
+        assert_eq!(expand_home("/tmp/list.m3u"), PathBuf::from("/tmp/list.m3u"));
+    }
+}
@@ -1,66 +1,432 @@
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlaybackState {
+    Connecting,
+    Playing,
+    #[default]
+    Stopped,
+    Error,
+}
+
 #[cfg(not(target_os = "windows"))]
 mod imp {
-    use std::io::Write;
-    use std::process::{Child, ChildStdin, Command, Stdio};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::os::fd::OwnedFd;
+    use std::os::unix::net::UnixStream;
+    use std::path::{Path, PathBuf};
+    use std::process::{Child, Command, Stdio};
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use super::PlaybackState;
+
+    const RC_QUERY_TIMEOUT: Duration = Duration::from_millis(500);
+    const RC_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(3);
+    const QUIT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+    const TITLE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+    const STATE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+    const DEAD_STREAM_WINDOW: Duration = Duration::from_secs(2);
+    const MPV_TITLE_REQUEST_ID: u64 = 1;
+    const NOT_FOUND_MESSAGE: &str =
+        "No audio player found (tried cvlc, mpv). Please install VLC or mpv: sudo apt install vlc";
+    const RC_DISABLED_MESSAGE: &str = "VLC is not answering on its rc interface, so volume and pause won't work. Install a VLC build with the rc (oldrc) interface enabled";
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Backend {
+        Vlc,
+        Mpv,
+    }
+
+    const BACKENDS: [Backend; 2] = [Backend::Vlc, Backend::Mpv];
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum PlayerCommand {
+        Volume(u8),
+        Pause,
+        Resume,
+        Quit,
+        Status,
+        SeekTo(i64),
+        SeekBy(i64),
+        GetLength,
+        GetTime,
+        GetTitle,
+    }
+
+    impl Backend {
+        fn program(self) -> &'static str {
+            match self {
+                Self::Vlc => "cvlc",
+                Self::Mpv => "mpv",
+            }
+        }
+
+        fn is_installed(self) -> bool {
+            std::env::var_os("PATH").is_some_and(|path| {
+                std::env::split_paths(&path).any(|dir| dir.join(self.program()).is_file())
+            })
+        }
+
+        fn path_entry(self) -> Option<PathBuf> {
+            let path = std::env::var_os("PATH")?;
+            std::env::split_paths(&path)
+                .map(|dir| dir.join(self.program()))
+                .find(|candidate| candidate.symlink_metadata().is_ok())
+        }
+
+        fn detect() -> Option<Self> {
+            BACKENDS.into_iter().find(|backend| backend.is_installed())
+        }
+
+        fn args(self, url: &str, volume: u8, output: Option<&str>) -> Vec<String> {
+            match self {
+                Self::Vlc => cvlc_args(url, volume, output),
+                Self::Mpv => mpv_args(url, volume, output),
+            }
+        }
+
+        fn record_args(self, url: &str, path: &str) -> Vec<String> {
+            match self {
+                Self::Vlc => ["--intf", "dummy", "--no-video", "--quiet"]
+                    .into_iter()
+                    .map(String::from)
+                    .chain([sout_to_file(path), url.to_string()])
+                    .collect(),
+                Self::Mpv => ["--no-video", "--no-terminal", "--ao=null"]
+                    .into_iter()
+                    .map(String::from)
+                    .chain([format!("--stream-record={}", path), url.to_string()])
+                    .collect(),
+            }
+        }
+
+        fn format_command(self, command: PlayerCommand) -> Option<String> {
+            let line = match (self, command) {
+                (Self::Vlc, PlayerCommand::Volume(volume)) => {
+                    return Some(vlc_volume_command(volume));
+                }
+                (Self::Vlc, PlayerCommand::Pause) => "pause".to_string(),
+                (Self::Vlc, PlayerCommand::Resume) => "play".to_string(),
+                (Self::Vlc, PlayerCommand::Status) => "status".to_string(),
+                (Self::Vlc, PlayerCommand::SeekTo(seconds)) => format!("seek {}", seconds),
+                (Self::Vlc, PlayerCommand::GetLength) => "get_length".to_string(),
+                (Self::Vlc, PlayerCommand::GetTime) => "get_time".to_string(),
+                (Self::Vlc, PlayerCommand::GetTitle) => "info".to_string(),
+                (Self::Mpv, PlayerCommand::GetTitle) => format!(
+                    "{{\"command\":[\"get_property\",\"metadata/by-key/icy-title\"],\"request_id\":{}}}",
+                    MPV_TITLE_REQUEST_ID
+                ),
+                (Self::Mpv, PlayerCommand::Volume(volume)) => format!("set volume {}", volume),
+                (Self::Mpv, PlayerCommand::Pause) => "set pause yes".to_string(),
+                (Self::Mpv, PlayerCommand::Resume) => "set pause no".to_string(),
+                (Self::Mpv, PlayerCommand::SeekBy(seconds)) => format!("seek {}", seconds),
+                (_, PlayerCommand::Quit) => "quit".to_string(),
+                _ => return None,
+            };
+            Some(format!("{}\n", line))
+        }
+    }
 
     pub struct Player {
+        backend: Backend,
         process: Option<Child>,
-        stdin: Option<ChildStdin>,
+        stdin: Option<Box<dyn Write + Send>>,
+        responses: Option<Receiver<String>>,
+        rc_probe: Option<Instant>,
+        title: Option<String>,
+        title_query: Option<Instant>,
+        state: PlaybackState,
+        started: Option<Instant>,
+        state_query: Option<Instant>,
         pub volume: u8,
+        pub audio_device: Option<String>,
+        pub quit_grace: Option<Duration>,
+        pub output: Option<String>,
+        recorder: Option<(Child, PathBuf)>,
+        quitting: Vec<thread::JoinHandle<()>>,
+        paused: bool,
+        seek: Option<PendingSeek>,
+        seek_failed: bool,
+    }
+
+    struct PendingSeek {
+        seconds: i64,
+        sent: Instant,
+        replies: Vec<i64>,
     }
 
     impl Player {
         pub fn new() -> Self {
             Self {
+                backend: Backend::detect().unwrap_or(Backend::Vlc),
                 process: None,
                 stdin: None,
+                responses: None,
+                rc_probe: None,
+                title: None,
+                title_query: None,
+                state: PlaybackState::Stopped,
+                started: None,
+                state_query: None,
                 volume: 50,
+                audio_device: None,
+                quit_grace: None,
+                output: None,
+                recorder: None,
+                quitting: Vec::new(),
+                paused: false,
+                seek: None,
+                seek_failed: false,
             }
         }
 
         pub fn play(&mut self, url: &str) -> Option<String> {
             self.stop();
-            let vol_arg = vlc_volume_from_percent(self.volume).to_string();
-            let mut cmd = Command::new("cvlc");
-            cmd.args([
-                "--no-video",
-                "--quiet",
-                "--intf",
-                "rc",
-                "--rc-fake-tty",
-                "--volume",
-                &vol_arg,
-                url,
-            ])
-            .stdin(Stdio::piped())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null());
-
-            match cmd.spawn() {
-                Ok(mut child) => {
-                    self.stdin = child.stdin.take();
-                    self.process = Some(child);
-                    None
+            if !self.backend.is_installed()
+                && let Some(backend) = Backend::detect()
+            {
+                self.backend = backend;
+            }
+            let preferred = self.backend;
+            let fallbacks = BACKENDS
+                .into_iter()
+                .filter(|backend| *backend != preferred && backend.is_installed());
+
+            let mut first_error = None;
+            for backend in std::iter::once(preferred).chain(fallbacks) {
+                let mut cmd = Command::new(backend.program());
+                cmd.args(backend.args(url, self.volume, self.output.as_deref()))
+                    .stderr(Stdio::null());
+                let ipc = match backend {
+                    Backend::Vlc => {
+                        cmd.stdin(Stdio::piped()).stdout(Stdio::piped());
+                        None
+                    }
+                    Backend::Mpv => match UnixStream::pair() {
+                        Ok((ours, theirs)) => {
+                            cmd.stdin(Stdio::from(OwnedFd::from(theirs)))
+                                .stdout(Stdio::null());
+                            Some(ours)
+                        }
+                        Err(e) => {
+                            first_error.get_or_insert_with(|| spawn_error_message(backend, &e));
+                            continue;
+                        }
+                    },
+                };
+                if let Some(device) = &self.audio_device {
+                    cmd.env("PULSE_SINK", device);
                 }
-                Err(e) => {
-                    self.process = None;
-                    self.stdin = None;
-                    let msg = if e.kind() == std::io::ErrorKind::NotFound {
-                        "cvlc not found. Please install VLC: sudo apt install vlc".to_string()
-                    } else {
-                        format!("Failed to start cvlc: {}", e)
-                    };
-                    Some(msg)
+
+                match cmd.spawn() {
+                    Ok(child) => {
+                        self.backend = backend;
+                        self.attach(child, ipc);
+                        return None;
+                    }
+                    Err(e) => {
+                        first_error.get_or_insert_with(|| spawn_error_message(backend, &e));
+                    }
                 }
             }
+            self.process = None;
+            self.stdin = None;
+            self.state = PlaybackState::Error;
+            first_error
+        }
+
+        fn attach(&mut self, mut child: Child, ipc: Option<UnixStream>) {
+            let output: Option<Box<dyn Read + Send>> = match ipc {
+                Some(stream) => {
+                    self.stdin = stream
+                        .try_clone()
+                        .ok()
+                        .map(|input| Box::new(input) as Box<dyn Write + Send>);
+                    Some(Box::new(stream))
+                }
+                None => {
+                    self.stdin = child
+                        .stdin
+                        .take()
+                        .map(|input| Box::new(input) as Box<dyn Write + Send>);
+                    child
+                        .stdout
+                        .take()
+                        .map(|stdout| Box::new(stdout) as Box<dyn Read + Send>)
+                }
+            };
+            self.responses = output.map(|output| {
+                let (tx, rx) = mpsc::channel();
+                thread::spawn(move || {
+                    for line in BufReader::new(output).lines() {
+                        let Ok(line) = line else { break };
+                        if tx.send(line).is_err() {
+                            break;
+                        }
+                    }
+                });
+                rx
+            });
+            self.process = Some(child);
+            self.state = PlaybackState::Connecting;
+            self.started = Some(Instant::now());
+            self.state_query = None;
+            let _ = self.send_command(PlayerCommand::Volume(self.volume));
+            if self.send_command(PlayerCommand::Status).is_ok() {
+                self.rc_probe = Some(Instant::now());
+            }
+        }
+
+        pub fn start_recording(&mut self, url: &str, path: &Path) -> Result<(), String> {
+            self.stop_recording();
+            if !self.backend.is_installed()
+                && let Some(backend) = Backend::detect()
+            {
+                self.backend = backend;
+            }
+            let child = Command::new(self.backend.program())
+                .args(self.backend.record_args(url, &path.to_string_lossy()))
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|e| spawn_error_message(self.backend, &e))?;
+            self.recorder = Some((child, path.to_path_buf()));
+            Ok(())
+        }
+
+        pub fn stop_recording(&mut self) -> Option<PathBuf> {
+            let (mut child, path) = self.recorder.take()?;
+            let _ = child.kill();
+            let _ = child.wait();
+            Some(path)
+        }
+
+        pub fn is_recording(&mut self) -> bool {
+            let exited = self
+                .recorder
+                .as_mut()
+                .is_some_and(|(child, _)| !matches!(child.try_wait(), Ok(None)));
+            if exited {
+                self.recorder = None;
+            }
+            self.recorder.is_some()
         }
 
         pub fn stop(&mut self) {
+            self.stop_recording();
             if let Some(mut child) = self.process.take() {
-                let _ = child.kill();
-                let _ = child.wait();
+                let grace = self
+                    .quit_grace
+                    .filter(|_| self.send_command(PlayerCommand::Quit).is_ok());
+                match grace {
+                    Some(grace) => {
+                        self.quitting.retain(|handle| !handle.is_finished());
+                        self.quitting.push(thread::spawn(move || {
+                            if !wait_for_exit(&mut child, grace) {
+                                let _ = child.kill();
+                            }
+                            let _ = child.wait();
+                        }));
+                    }
+                    None => {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                    }
+                }
             }
             self.stdin = None;
+            self.responses = None;
+            self.rc_probe = None;
+            self.title = None;
+            self.title_query = None;
+            self.state = PlaybackState::Stopped;
+            self.started = None;
+            self.state_query = None;
+            self.paused = false;
+            self.seek = None;
+        }
+
+        pub fn poll_metadata(&mut self) {
+            if self.process.is_none() {
+                return;
+            }
+            let due = self.seek.is_none()
+                && self
+                    .title_query
+                    .is_none_or(|sent| sent.elapsed() >= TITLE_POLL_INTERVAL);
+            if due && self.send_command(PlayerCommand::GetTitle).is_ok() {
+                self.title_query = Some(Instant::now());
+            }
+            let state_due = self
+                .state_query
+                .is_none_or(|sent| sent.elapsed() >= STATE_POLL_INTERVAL);
+            if self.state == PlaybackState::Connecting
+                && self.seek.is_none()
+                && state_due
+                && self.send_command(PlayerCommand::Status).is_ok()
+            {
+                self.state_query = Some(Instant::now());
+            }
+            let Some(responses) = &self.responses else {
+                return;
+            };
+            let mut answered = false;
+            let mut title = None;
+            let mut state = None;
+            while let Ok(line) = responses.try_recv() {
+                answered = true;
+                if let Some(pending) = &mut self.seek
+                    && let Some(number) = parse_rc_number(&line)
+                {
+                    pending.replies.push(number);
+                }
+                let (parsed, parsed_state) = match self.backend {
+                    Backend::Vlc => (parse_vlc_title(&line), parse_vlc_state(&line)),
+                    Backend::Mpv => (parse_mpv_title(&line), parse_mpv_state(&line)),
+                };
+                if parsed.is_some() {
+                    title = parsed;
+                }
+                if parsed_state.is_some() {
+                    state = parsed_state;
+                }
+            }
+            match state {
+                Some(PlaybackState::Stopped) if self.state == PlaybackState::Connecting => {}
+                Some(state) => self.state = state,
+                None => {}
+            }
+            if answered {
+                self.rc_probe = None;
+            }
+            if title.is_some() {
+                self.title = title;
+            }
+            self.finish_seek();
+        }
+
+        fn finish_seek(&mut self) {
+            let Some(pending) = self.seek.take() else {
+                return;
+            };
+            match pending.replies[..] {
+                [length, time, ..] if length > 0 => {
+                    let target = (time + pending.seconds).clamp(0, length);
+                    self.seek_failed = self.send_command(PlayerCommand::SeekTo(target)).is_err();
+                }
+                [_, _, ..] => self.seek_failed = true,
+                _ if pending.sent.elapsed() >= RC_QUERY_TIMEOUT => self.seek_failed = true,
+                _ => self.seek = Some(pending),
+            }
+        }
+
+        pub fn take_seek_failure(&mut self) -> bool {
+            std::mem::take(&mut self.seek_failed)
+        }
+
+        pub fn now_playing_title(&self) -> Option<String> {
+            self.title.clone()
         }
 
         #[allow(dead_code)]
@@ -68,29 +434,129 @@ mod imp {
             self.process.is_some()
         }
 
+        pub fn state(&self) -> PlaybackState {
+            self.state
+        }
+
+        pub fn has_exited(&mut self) -> bool {
+            let exited = self
+                .process
+                .as_mut()
+                .is_some_and(|child| !matches!(child.try_wait(), Ok(None)));
+            if exited {
+                let dead_stream = self
+                    .started
+                    .is_some_and(|started| started.elapsed() < DEAD_STREAM_WINDOW);
+                self.stop();
+                if dead_stream {
+                    self.state = PlaybackState::Error;
+                }
+            }
+            exited
+        }
+
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        pub fn resume(&mut self) -> bool {
+            if !self.paused || self.send_command(PlayerCommand::Resume).is_err() {
+                return false;
+            }
+            self.paused = false;
+            true
+        }
+
+        pub fn toggle_pause(&mut self) -> bool {
+            if self.paused {
+                return self.resume();
+            }
+            if self.process.is_none() || self.send_command(PlayerCommand::Pause).is_err() {
+                return false;
+            }
+            self.paused = true;
+            true
+        }
+
         pub fn volume_up(&mut self) {
             if self.volume < 100 {
                 self.volume = (self.volume + 5).min(100);
-                let _ = self.send_vlc_command(&vlc_volume_command(self.volume));
+                let _ = self.send_command(PlayerCommand::Volume(self.volume));
             }
         }
 
         pub fn volume_down(&mut self) {
             if self.volume > 0 {
                 self.volume = self.volume.saturating_sub(5);
-                let _ = self.send_vlc_command(&vlc_volume_command(self.volume));
+                let _ = self.send_command(PlayerCommand::Volume(self.volume));
+            }
+        }
+
+        pub fn check_rc_interface(&mut self) -> Option<String> {
+            let sent = self.rc_probe?;
+            let answered = self
+                .responses
+                .as_ref()
+                .is_some_and(|responses| responses.try_recv().is_ok());
+            if answered {
+                self.rc_probe = None;
+                return None;
             }
+            if sent.elapsed() < RC_HANDSHAKE_TIMEOUT {
+                return None;
+            }
+            self.rc_probe = None;
+            if self.state == PlaybackState::Connecting {
+                self.state = PlaybackState::Playing;
+            }
+            Some(RC_DISABLED_MESSAGE.to_string())
+        }
+
+        pub fn seek(&mut self, seconds: i64) -> bool {
+            if self.backend == Backend::Mpv {
+                return self.send_command(PlayerCommand::SeekBy(seconds)).is_ok();
+            }
+            if let Some(pending) = &mut self.seek {
+                pending.seconds += seconds;
+                return true;
+            }
+            self.rc_probe = None;
+            if let Some(responses) = &self.responses {
+                while responses.try_recv().is_ok() {}
+            }
+            if self.send_command(PlayerCommand::GetLength).is_err()
+                || self.send_command(PlayerCommand::GetTime).is_err()
+            {
+                return false;
+            }
+            self.seek = Some(PendingSeek {
+                seconds,
+                sent: Instant::now(),
+                replies: Vec::new(),
+            });
+            true
         }
 
-        fn send_vlc_command(&mut self, cmd: &str) -> std::io::Result<()> {
+        pub fn set_volume(&mut self, percent: u8) {
+            self.volume = percent.min(100);
+            let _ = self.send_command(PlayerCommand::Volume(self.volume));
+        }
+
+        fn send_command(&mut self, command: PlayerCommand) -> std::io::Result<()> {
+            let Some(line) = self.backend.format_command(command) else {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Unsupported,
+                    format!("{} does not support {:?}", self.backend.program(), command),
+                ));
+            };
             if let Some(stdin) = &mut self.stdin {
-                stdin.write_all(cmd.as_bytes())?;
+                stdin.write_all(line.as_bytes())?;
                 stdin.flush()?;
                 Ok(())
             } else {
                 Err(std::io::Error::new(
                     std::io::ErrorKind::BrokenPipe,
-                    "cvlc stdin not available",
+                    format!("{} stdin not available", self.backend.program()),
                 ))
             }
         }
@@ -99,27 +565,228 @@ mod imp {
     impl Drop for Player {
         fn drop(&mut self) {
             self.stop();
+            for handle in self.quitting.drain(..) {
+                let _ = handle.join();
+            }
+        }
+    }
+
+    fn wait_for_exit(child: &mut Child, grace: Duration) -> bool {
+        let deadline = Instant::now() + grace;
+        loop {
+            if !matches!(child.try_wait(), Ok(None)) {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            thread::sleep(QUIT_POLL_INTERVAL);
+        }
+    }
+
+    fn cvlc_args(url: &str, volume: u8, output: Option<&str>) -> Vec<String> {
+        let mut args: Vec<String> = [
+            "--no-video",
+            "--quiet",
+            "--intf",
+            "rc",
+            "--rc-fake-tty",
+            "--volume",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        args.push(vlc_volume_from_percent(volume).to_string());
+        if let Some(path) = output {
+            args.push(sout_to_file(path));
+        }
+        args.push(url.to_string());
+        args
+    }
+
+    fn mpv_args(url: &str, volume: u8, output: Option<&str>) -> Vec<String> {
+        let mut args: Vec<String> = ["--no-video", "--no-terminal", "--input-ipc-client=fd://0"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        args.push(format!("--volume={}", volume));
+        if let Some(path) = output {
+            args.push("--ao=null".to_string());
+            args.push(format!("--stream-record={}", path));
+        }
+        args.push(url.to_string());
+        args
+    }
+
+    fn sout_to_file(path: &str) -> String {
+        format!(
+            "--sout=#standard{{access=file,mux=raw,dst=\"{}\"}}",
+            path.replace('\\', "\\\\").replace('"', "\\\"")
+        )
+    }
+
+    fn spawn_error_message(backend: Backend, err: &std::io::Error) -> String {
+        let program = backend.program();
+        match (err.kind(), backend.path_entry()) {
+            (std::io::ErrorKind::NotFound, None) => NOT_FOUND_MESSAGE.to_string(),
+            (std::io::ErrorKind::NotFound, Some(path)) => format!(
+                "{} exists but could not be run. It may be a broken symlink or need a missing interpreter; reinstall {}",
+                path.display(),
+                program
+            ),
+            (std::io::ErrorKind::PermissionDenied, path) => format!(
+                "{} is not executable (permission denied). Fix it with: chmod +x {}",
+                program,
+                path.map(|path| path.display().to_string())
+                    .unwrap_or_else(|| program.to_string())
+            ),
+            _ => format!(
+                "Failed to start {}: {}. Run `{} --version` in a terminal to see what is wrong",
+                program, err, program
+            ),
         }
     }
 
     fn vlc_volume_from_percent(volume: u8) -> u32 {
-        (volume as u32 * 256) / 100
+        (volume as u32 * 256 + 50) / 100
     }
 
     fn vlc_volume_command(volume: u8) -> String {
         format!("volume {}\n", vlc_volume_from_percent(volume))
     }
 
+    fn parse_vlc_title(line: &str) -> Option<String> {
+        let (key, value) = line.trim_start_matches(['>', ' ', '|']).split_once(':')?;
+        let key = key.trim().to_lowercase();
+        if key != "now_playing" && key != "now playing" {
+            return None;
+        }
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    fn parse_mpv_title(line: &str) -> Option<String> {
+        let reply: serde_json::Value = serde_json::from_str(line).ok()?;
+        if reply.get("request_id")?.as_u64()? != MPV_TITLE_REQUEST_ID {
+            return None;
+        }
+        let title = reply.get("data")?.as_str()?.trim();
+        (!title.is_empty()).then(|| title.to_string())
+    }
+
+    fn parse_vlc_state(line: &str) -> Option<PlaybackState> {
+        let status = line.trim_start_matches(['>', ' ']).trim();
+        let state = status.strip_prefix("( state ")?.strip_suffix(')')?.trim();
+        match state {
+            "opening" | "buffering" => Some(PlaybackState::Connecting),
+            "playing" | "paused" => Some(PlaybackState::Playing),
+            "stopped" | "ended" => Some(PlaybackState::Stopped),
+            "error" => Some(PlaybackState::Error),
+            _ => None,
+        }
+    }
+
+    fn parse_mpv_state(line: &str) -> Option<PlaybackState> {
+        let event: serde_json::Value = serde_json::from_str(line).ok()?;
+        match (
+            event.get("event")?.as_str()?,
+            event.get("reason").and_then(|reason| reason.as_str()),
+        ) {
+            ("playback-restart", _) => Some(PlaybackState::Playing),
+            ("end-file", Some("error")) => Some(PlaybackState::Error),
+            _ => None,
+        }
+    }
+
+    fn parse_rc_number(line: &str) -> Option<i64> {
+        line.trim_start_matches(['>', ' ']).trim().parse().ok()
+    }
+
     #[cfg(test)]
     mod tests {
-        use super::{Player, vlc_volume_command, vlc_volume_from_percent};
+        use super::{
+            Backend, NOT_FOUND_MESSAGE, PlaybackState, Player, PlayerCommand, RC_DISABLED_MESSAGE,
+            RC_HANDSHAKE_TIMEOUT, cvlc_args, parse_mpv_state, parse_mpv_title, parse_rc_number,
+            parse_vlc_state, parse_vlc_title, vlc_volume_command, vlc_volume_from_percent,
+        };
+        use std::path::{Path, PathBuf};
         use std::sync::{Mutex, OnceLock};
+        use std::time::{Duration, Instant};
 
         fn env_lock() -> &'static Mutex<()> {
             static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
             LOCK.get_or_init(|| Mutex::new(()))
         }
 
+        fn fake_cvlc(name: &str, script: &str) -> PathBuf {
+            fake_program(name, "cvlc", script)
+        }
+
+        fn fake_program(name: &str, program: &str, script: &str) -> PathBuf {
+            fake_program_with_mode(name, program, script, 0o755)
+        }
+
+        fn fake_program_with_mode(name: &str, program: &str, script: &str, mode: u32) -> PathBuf {
+            use std::os::unix::fs::PermissionsExt;
+
+            let dir = std::env::temp_dir().join(format!(
+                "cradio-player-test-{}-{}",
+                name,
+                std::process::id()
+            ));
+            std::fs::create_dir_all(&dir).expect("create dir");
+            let path = dir.join(program);
+            std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).expect("write fake player");
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode))
+                .expect("set fake player permissions");
+            dir
+        }
+
+        fn play_with_path(player: &mut Player, path: &Path) -> Option<String> {
+            let original_path = std::env::var_os("PATH");
+            unsafe {
+                std::env::set_var("PATH", path);
+            }
+
+            let result = player.play("https://example.com/stream");
+
+            match original_path {
+                Some(path) => unsafe { std::env::set_var("PATH", path) },
+                None => unsafe { std::env::remove_var("PATH") },
+            }
+            result
+        }
+
+        fn read_when_written(path: &Path, expected_lines: usize) -> String {
+            let mut received = String::new();
+            for _ in 0..100 {
+                received = std::fs::read_to_string(path).unwrap_or_default();
+                if received.lines().count() >= expected_lines {
+                    break;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(20));
+            }
+            received
+        }
+
+        fn rc_script(length: u32, log: &Path) -> String {
+            format!(
+                "while read -r line; do\n  case \"$line\" in\n    get_length) echo {} ;;\n    get_time) echo '> 100' ;;\n    status) echo '( audio volume: 128 )' ;;\n    *) echo \"$line\" >> '{}' ;;\n  esac\ndone",
+                length,
+                log.display()
+            )
+        }
+
+        fn finish_seek(player: &mut Player) {
+            for _ in 0..100 {
+                player.poll_metadata();
+                if player.seek.is_none() {
+                    return;
+                }
+                std::thread::sleep(Duration::from_millis(20));
+            }
+        }
+
         #[test]
         fn player_starts_at_expected_volume() {
             let player = Player::new();
@@ -149,6 +816,93 @@ mod imp {
             assert!(!player.is_playing());
         }
 
+        #[test]
+        fn stop_is_idempotent() {
+            let mut player = Player::new();
+            player.volume = 70;
+            for _ in 0..3 {
+                player.stop();
+                assert!(!player.is_playing());
+                assert!(!player.is_paused());
+            }
+            assert_eq!(player.volume, 70);
+        }
+
+        #[test]
+        fn set_volume_jumps_directly_and_clamps() {
+            let mut player = Player::new();
+            player.set_volume(80);
+            assert_eq!(player.volume, 80);
+            player.set_volume(150);
+            assert_eq!(player.volume, 100);
+            player.set_volume(0);
+            assert_eq!(player.volume, 0);
+        }
+
+        #[test]
+        fn volume_steps_stop_exactly_at_bounds() {
+            let mut player = Player::new();
+            player.volume = 97;
+            player.volume_up();
+            assert_eq!(player.volume, 100);
+            player.volume_up();
+            assert_eq!(player.volume, 100);
+
+            player.volume = 3;
+            player.volume_down();
+            assert_eq!(player.volume, 0);
+            player.volume_down();
+            assert_eq!(player.volume, 0);
+        }
+
+        #[test]
+        fn resume_is_a_no_op_when_not_paused() {
+            let mut player = Player::new();
+            assert!(!player.is_paused());
+            assert!(!player.resume());
+        }
+
+        #[test]
+        fn resume_without_running_process_keeps_paused_state() {
+            let mut player = Player::new();
+            player.paused = true;
+            assert!(!player.resume());
+            assert!(player.is_paused());
+
+            player.stop();
+            assert!(!player.is_paused());
+        }
+
+        #[test]
+        fn toggle_pause_is_a_no_op_without_running_process() {
+            let mut player = Player::new();
+            assert!(!player.toggle_pause());
+            assert!(!player.is_paused());
+        }
+
+        #[test]
+        fn toggle_pause_pauses_and_resumes_over_rc() {
+            let _guard = env_lock().lock().expect("env lock");
+            let log = std::env::temp_dir().join(format!(
+                "cradio-player-test-pause-{}.txt",
+                std::process::id()
+            ));
+            let dir = fake_cvlc("pause", &rc_script(0, &log));
+
+            let mut player = Player::new();
+            assert_eq!(play_with_path(&mut player, &dir), None);
+            assert!(player.toggle_pause());
+            assert!(player.is_paused());
+            assert!(player.toggle_pause());
+            assert!(!player.is_paused());
+            let received = read_when_written(&log, 3);
+            player.stop();
+            let _ = std::fs::remove_dir_all(&dir);
+            let _ = std::fs::remove_file(&log);
+
+            assert_eq!(received, format!("{}pause\nplay\n", vlc_volume_command(50)));
+        }
+
         #[test]
         fn vlc_volume_mapping_matches_expected_bounds() {
             assert_eq!(vlc_volume_from_percent(0), 0);
@@ -156,21 +910,174 @@ mod imp {
             assert_eq!(vlc_volume_from_percent(100), 256);
         }
 
+        #[test]
+        fn vlc_volume_mapping_rounds_to_nearest() {
+            assert_eq!(vlc_volume_from_percent(1), 3);
+            assert_eq!(vlc_volume_from_percent(5), 13);
+            assert_eq!(vlc_volume_from_percent(25), 64);
+            assert_eq!(vlc_volume_from_percent(75), 192);
+            assert_eq!(vlc_volume_from_percent(99), 253);
+            assert_eq!(
+                vlc_volume_from_percent(50) * 2,
+                vlc_volume_from_percent(100)
+            );
+        }
+
+        #[test]
+        fn vlc_volume_mapping_is_monotonic() {
+            for volume in 0..100u8 {
+                assert!(vlc_volume_from_percent(volume) <= vlc_volume_from_percent(volume + 1));
+            }
+        }
+
         #[test]
         fn vlc_volume_command_formats_rc_input() {
             assert_eq!(vlc_volume_command(25), "volume 64\n");
         }
 
         #[test]
-        fn play_returns_install_hint_when_cvlc_is_not_on_path() {
+        fn commands_are_formatted_for_each_backend() {
+            assert_eq!(
+                Backend::Vlc.format_command(PlayerCommand::Volume(50)),
+                Some("volume 128\n".to_string())
+            );
+            assert_eq!(
+                Backend::Mpv.format_command(PlayerCommand::Volume(50)),
+                Some("set volume 50\n".to_string())
+            );
+            assert_eq!(
+                Backend::Mpv.format_command(PlayerCommand::Resume),
+                Some("set pause no\n".to_string())
+            );
+            assert_eq!(
+                Backend::Vlc.format_command(PlayerCommand::SeekTo(90)),
+                Some("seek 90\n".to_string())
+            );
+            assert_eq!(Backend::Vlc.format_command(PlayerCommand::SeekBy(10)), None);
+            assert_eq!(Backend::Mpv.format_command(PlayerCommand::Status), None);
+            assert_eq!(
+                Backend::Mpv.format_command(PlayerCommand::Quit),
+                Some("quit\n".to_string())
+            );
+        }
+
+        #[test]
+        fn play_falls_back_to_mpv_when_cvlc_is_missing() {
+            let _guard = env_lock().lock().expect("env lock");
+            let log = std::env::temp_dir()
+                .join(format!("cradio-player-test-mpv-{}.txt", std::process::id()));
+            let dir = fake_program(
+                "mpv",
+                "mpv",
+                &format!(
+                    "echo \"$@\" > '{}'\nexec /bin/cat >> '{}'",
+                    log.display(),
+                    log.display()
+                ),
+            );
+
+            let mut player = Player::new();
+            player.volume = 30;
+            assert_eq!(play_with_path(&mut player, &dir), None);
+            assert_eq!(player.backend, Backend::Mpv);
+            read_when_written(&log, 2);
+            assert!(player.seek(10));
+            let received = read_when_written(&log, 3);
+            player.stop();
+            let _ = std::fs::remove_dir_all(&dir);
+            let _ = std::fs::remove_file(&log);
+
+            assert_eq!(
+                received,
+                "--no-video --no-terminal --input-ipc-client=fd://0 --volume=30 https://example.com/stream\nset volume 30\nseek 10\n"
+            );
+        }
+
+        #[test]
+        fn cvlc_args_write_the_raw_stream_to_the_configured_output() {
+            let args = cvlc_args("https://example.com/stream", 50, None);
+            assert_eq!(
+                args.last().map(String::as_str),
+                Some("https://example.com/stream")
+            );
+            assert!(!args.iter().any(|arg| arg.starts_with("--sout")));
+
+            let args = cvlc_args(
+                "https://example.com/stream",
+                50,
+                Some("/tmp/my \"radio\".fifo"),
+            );
+            assert_eq!(
+                args[args.len() - 2],
+                "--sout=#standard{access=file,mux=raw,dst=\"/tmp/my \\\"radio\\\".fifo\"}"
+            );
+            assert_eq!(args[args.len() - 3], "128");
+        }
+
+        #[test]
+        fn recording_runs_a_separate_cvlc_until_stopped() {
+            let _guard = env_lock().lock().expect("env lock");
+            let log = std::env::temp_dir().join(format!(
+                "cradio-player-test-record-{}.txt",
+                std::process::id()
+            ));
+            let dir = fake_cvlc(
+                "record",
+                &format!("echo \"$@\" > '{}'\nexec /bin/sleep 30", log.display()),
+            );
+            let original_path = std::env::var_os("PATH");
+            unsafe {
+                std::env::set_var("PATH", &dir);
+            }
+
+            let mut player = Player::new();
+            let started =
+                player.start_recording("https://example.com/stream", Path::new("/rec/a.mp3"));
+
+            match original_path {
+                Some(path) => unsafe { std::env::set_var("PATH", path) },
+                None => unsafe { std::env::remove_var("PATH") },
+            }
+            assert_eq!(started, Ok(()));
+            assert!(player.is_recording());
+            let received = read_when_written(&log, 1);
+            player.stop();
+            let _ = std::fs::remove_dir_all(&dir);
+            let _ = std::fs::remove_file(&log);
+
+            assert!(!player.is_recording());
+            assert_eq!(player.stop_recording(), None);
+            assert_eq!(
+                received,
+                "--intf dummy --no-video --quiet --sout=#standard{access=file,mux=raw,dst=\"/rec/a.mp3\"} https://example.com/stream\n"
+            );
+        }
+
+        #[test]
+        fn play_pushes_volume_command_after_spawn() {
+            use std::os::unix::fs::PermissionsExt;
+
             let _guard = env_lock().lock().expect("env lock");
+            let dir = std::env::temp_dir()
+                .join(format!("cradio-player-test-volume-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).expect("create dir");
+            let output = dir.join("stdin.txt");
+            let script = dir.join("cvlc");
+            std::fs::write(
+                &script,
+                format!("#!/bin/sh\nexec /bin/cat > '{}'\n", output.display()),
+            )
+            .expect("write fake cvlc");
+            std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))
+                .expect("make fake cvlc executable");
             let original_path = std::env::var_os("PATH");
 
             unsafe {
-                std::env::set_var("PATH", "");
+                std::env::set_var("PATH", &dir);
             }
 
             let mut player = Player::new();
+            player.volume = 30;
             let result = player.play("https://example.com/stream");
 
             match original_path {
@@ -178,18 +1085,401 @@ mod imp {
                 None => unsafe { std::env::remove_var("PATH") },
             }
 
+            assert_eq!(result, None);
+            let mut received = String::new();
+            for _ in 0..100 {
+                received = std::fs::read_to_string(&output).unwrap_or_default();
+                if !received.is_empty() {
+                    break;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(20));
+            }
+            player.stop();
+            let _ = std::fs::remove_dir_all(&dir);
+
+            assert_eq!(received, format!("{}status\n", vlc_volume_command(30)));
+        }
+
+        #[test]
+        fn stop_sends_quit_and_waits_when_a_grace_period_is_set() {
+            let _guard = env_lock().lock().expect("env lock");
+            let log = std::env::temp_dir().join(format!(
+                "cradio-player-test-quit-{}.txt",
+                std::process::id()
+            ));
+            let dir = fake_cvlc(
+                "quit",
+                &format!(
+                    "while read -r line; do\n  if [ \"$line\" = quit ]; then echo quit > '{}'; exit 0; fi\ndone",
+                    log.display()
+                ),
+            );
+
+            let mut player = Player::new();
+            player.quit_grace = Some(Duration::from_secs(5));
+            assert_eq!(play_with_path(&mut player, &dir), None);
+            player.stop();
+            assert!(!player.is_playing());
+            drop(player);
+            let received = std::fs::read_to_string(&log).unwrap_or_default();
+            let _ = std::fs::remove_dir_all(&dir);
+            let _ = std::fs::remove_file(&log);
+
+            assert_eq!(received, "quit\n");
+        }
+
+        #[test]
+        fn stop_returns_at_once_and_kills_cvlc_that_ignores_quit_after_the_grace_period() {
+            let _guard = env_lock().lock().expect("env lock");
+            let dir = fake_cvlc("stubborn", "exec /bin/sleep 10");
+
+            let mut player = Player::new();
+            player.quit_grace = Some(Duration::from_millis(300));
+            assert_eq!(play_with_path(&mut player, &dir), None);
+            let started = Instant::now();
+            player.stop();
+            let stopped = started.elapsed();
+            assert!(!player.is_playing());
+            drop(player);
+            let reaped = started.elapsed();
+            let _ = std::fs::remove_dir_all(&dir);
+
+            assert!(stopped < Duration::from_millis(300));
+            assert!(reaped >= Duration::from_millis(300));
+            assert!(reaped < Duration::from_secs(2));
+        }
+
+        #[test]
+        fn seek_moves_relative_to_the_current_position_within_bounds() {
+            let _guard = env_lock().lock().expect("env lock");
+            let log = std::env::temp_dir().join(format!(
+                "cradio-player-test-seek-{}.txt",
+                std::process::id()
+            ));
+            let dir = fake_cvlc("seek", &rc_script(3600, &log));
+
+            let mut player = Player::new();
+            assert_eq!(play_with_path(&mut player, &dir), None);
+            assert!(player.seek(10));
+            finish_seek(&mut player);
+            assert!(player.seek(-500));
+            finish_seek(&mut player);
+            let received = read_when_written(&log, 3);
+            player.stop();
+            let _ = std::fs::remove_dir_all(&dir);
+            let _ = std::fs::remove_file(&log);
+
+            assert_eq!(
+                received,
+                format!("{}seek 110\nseek 0\n", vlc_volume_command(50))
+            );
+        }
+
+        #[test]
+        fn rc_interface_is_confirmed_by_any_response() {
+            let _guard = env_lock().lock().expect("env lock");
+            let log = std::env::temp_dir()
+                .join(format!("cradio-player-test-rc-{}.txt", std::process::id()));
+            let dir = fake_cvlc("rc", &rc_script(0, &log));
+
+            let mut player = Player::new();
+            assert_eq!(play_with_path(&mut player, &dir), None);
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            assert_eq!(player.check_rc_interface(), None);
+            player.rc_probe = std::time::Instant::now().checked_sub(RC_HANDSHAKE_TIMEOUT * 2);
+            player.stop();
+            let _ = std::fs::remove_dir_all(&dir);
+            let _ = std::fs::remove_file(&log);
+
+            assert_eq!(player.check_rc_interface(), None);
+        }
+
+        #[test]
+        fn silent_rc_interface_is_reported_once() {
+            let _guard = env_lock().lock().expect("env lock");
+            let dir = fake_cvlc("silent", "exec /bin/cat > /dev/null");
+
+            let mut player = Player::new();
+            assert_eq!(play_with_path(&mut player, &dir), None);
+            assert_eq!(player.check_rc_interface(), None);
+            player.rc_probe = std::time::Instant::now().checked_sub(RC_HANDSHAKE_TIMEOUT * 2);
+            let message = player.check_rc_interface();
+            let repeated = player.check_rc_interface();
+            player.stop();
+            let _ = std::fs::remove_dir_all(&dir);
+
+            assert_eq!(message.as_deref(), Some(RC_DISABLED_MESSAGE));
+            assert_eq!(repeated, None);
+        }
+
+        #[test]
+        fn seek_is_a_no_op_for_live_streams() {
+            let _guard = env_lock().lock().expect("env lock");
+            let log = std::env::temp_dir().join(format!(
+                "cradio-player-test-live-{}.txt",
+                std::process::id()
+            ));
+            let dir = fake_cvlc("live", &rc_script(0, &log));
+
+            let mut player = Player::new();
+            assert_eq!(play_with_path(&mut player, &dir), None);
+            assert!(player.seek(10));
+            finish_seek(&mut player);
+            assert!(player.take_seek_failure());
+            let received = read_when_written(&log, 1);
+            player.stop();
+            let _ = std::fs::remove_dir_all(&dir);
+            let _ = std::fs::remove_file(&log);
+
+            assert!(!received.contains("seek"));
+            assert!(!Player::new().seek(10));
+        }
+
+        #[test]
+        fn play_routes_audio_to_the_configured_device() {
+            let _guard = env_lock().lock().expect("env lock");
+            let log = std::env::temp_dir().join(format!(
+                "cradio-player-test-device-{}.txt",
+                std::process::id()
+            ));
+            let dir = fake_cvlc(
+                "device",
+                &format!(
+                    "echo \"$PULSE_SINK\" > '{}'\nexec /bin/cat > /dev/null",
+                    log.display()
+                ),
+            );
+
+            let mut player = Player::new();
+            player.audio_device = Some("headphones".to_string());
+            assert_eq!(play_with_path(&mut player, &dir), None);
+            let received = read_when_written(&log, 1);
+            player.stop();
+            let _ = std::fs::remove_dir_all(&dir);
+            let _ = std::fs::remove_file(&log);
+
+            assert_eq!(received, "headphones\n");
+        }
+
+        #[test]
+        fn has_exited_detects_a_dropped_stream_once() {
+            let _guard = env_lock().lock().expect("env lock");
+            let dir = fake_cvlc("exit", "exit 1");
+
+            let mut player = Player::new();
+            assert!(!player.has_exited());
+            assert_eq!(play_with_path(&mut player, &dir), None);
+            let mut exited = false;
+            for _ in 0..100 {
+                if player.has_exited() {
+                    exited = true;
+                    break;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(20));
+            }
+            let _ = std::fs::remove_dir_all(&dir);
+
+            assert!(exited);
+            assert!(!player.is_playing());
+            assert_eq!(player.state(), PlaybackState::Error);
+            assert!(!player.has_exited());
+        }
+
+        #[test]
+        fn playback_state_is_parsed_from_vlc_status_and_mpv_events() {
+            assert_eq!(
+                parse_vlc_state("> ( state playing )"),
+                Some(PlaybackState::Playing)
+            );
+            assert_eq!(
+                parse_vlc_state("( state buffering )"),
+                Some(PlaybackState::Connecting)
+            );
+            assert_eq!(
+                parse_vlc_state("( state stopped )"),
+                Some(PlaybackState::Stopped)
+            );
+            assert_eq!(parse_vlc_state("( audio volume: 128 )"), None);
+            assert_eq!(
+                parse_mpv_state(r#"{"event":"playback-restart"}"#),
+                Some(PlaybackState::Playing)
+            );
+            assert_eq!(
+                parse_mpv_state(r#"{"event":"end-file","reason":"error"}"#),
+                Some(PlaybackState::Error)
+            );
+            assert_eq!(
+                parse_mpv_state(r#"{"event":"end-file","reason":"quit"}"#),
+                None
+            );
+        }
+
+        #[test]
+        fn poll_metadata_confirms_playback_from_vlc_status() {
+            let _guard = env_lock().lock().expect("env lock");
+            let dir = fake_cvlc(
+                "state",
+                "while read -r line; do\n  if [ \"$line\" = status ]; then printf '( state stopped )\\n( state playing )\\n'; fi\ndone",
+            );
+
+            let mut player = Player::new();
+            assert_eq!(player.state(), PlaybackState::Stopped);
+            assert_eq!(play_with_path(&mut player, &dir), None);
+            assert_eq!(player.state(), PlaybackState::Connecting);
+            for _ in 0..100 {
+                player.poll_metadata();
+                if player.state() == PlaybackState::Playing {
+                    break;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(20));
+            }
+            let state = player.state();
+            player.stop();
+            let _ = std::fs::remove_dir_all(&dir);
+
+            assert_eq!(state, PlaybackState::Playing);
+            assert_eq!(player.state(), PlaybackState::Stopped);
+        }
+
+        #[test]
+        fn stream_titles_are_parsed_from_vlc_info_and_mpv_replies() {
+            assert_eq!(
+                parse_vlc_title("| now_playing: Miles Davis - So What"),
+                Some("Miles Davis - So What".to_string())
+            );
+            assert_eq!(
+                parse_vlc_title("> | Now Playing: A: B"),
+                Some("A: B".to_string())
+            );
+            assert_eq!(parse_vlc_title("| now_playing: "), None);
+            assert_eq!(parse_vlc_title("| title: Jazz FM"), None);
+            assert_eq!(
+                parse_mpv_title(r#"{"data":"Artist - Song","request_id":1,"error":"success"}"#),
+                Some("Artist - Song".to_string())
+            );
             assert_eq!(
-                result,
-                Some("cvlc not found. Please install VLC: sudo apt install vlc".to_string())
+                parse_mpv_title(r#"{"request_id":1,"error":"property unavailable"}"#),
+                None
             );
+            assert_eq!(parse_mpv_title(r#"{"data":"other","request_id":0}"#), None);
+        }
+
+        #[test]
+        fn poll_metadata_reads_the_track_from_vlc_info() {
+            let _guard = env_lock().lock().expect("env lock");
+            let dir = fake_cvlc(
+                "title",
+                "while read -r line; do\n  if [ \"$line\" = info ]; then printf '+----[ Meta data ]\\n| title: Jazz FM\\n| now_playing: Miles Davis - So What\\n'; fi\ndone",
+            );
+
+            let mut player = Player::new();
+            assert_eq!(player.now_playing_title(), None);
+            assert_eq!(play_with_path(&mut player, &dir), None);
+            let mut title = None;
+            for _ in 0..100 {
+                player.poll_metadata();
+                title = player.now_playing_title();
+                if title.is_some() {
+                    break;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(20));
+            }
+            player.stop();
+            let _ = std::fs::remove_dir_all(&dir);
+
+            assert_eq!(title.as_deref(), Some("Miles Davis - So What"));
+            assert_eq!(player.now_playing_title(), None);
+        }
+
+        #[test]
+        fn rc_numbers_are_parsed_with_or_without_a_prompt() {
+            assert_eq!(parse_rc_number("3600"), Some(3600));
+            assert_eq!(parse_rc_number("> 42 "), Some(42));
+            assert_eq!(parse_rc_number("status change: ( play state: 3 )"), None);
+        }
+
+        #[test]
+        fn play_explains_a_player_without_execute_permission() {
+            let _guard = env_lock().lock().expect("env lock");
+            let dir = fake_program_with_mode("noexec", "cvlc", "exit 0", 0o644);
+
+            let mut player = Player::new();
+            let result = play_with_path(&mut player, &dir).expect("spawn fails");
+            let _ = std::fs::remove_dir_all(&dir);
+
+            assert!(result.starts_with("cvlc is not executable (permission denied)"));
+            assert!(result.contains(&format!("chmod +x {}", dir.join("cvlc").display())));
+            assert!(!player.is_playing());
+        }
+
+        #[test]
+        fn play_explains_a_broken_player_symlink() {
+            let _guard = env_lock().lock().expect("env lock");
+            let dir = std::env::temp_dir()
+                .join(format!("cradio-player-test-broken-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).expect("create dir");
+            let link = dir.join("cvlc");
+            let _ = std::fs::remove_file(&link);
+            std::os::unix::fs::symlink(dir.join("missing-vlc"), &link).expect("symlink");
+
+            let mut player = Player::new();
+            let result = play_with_path(&mut player, &dir).expect("spawn fails");
+            let _ = std::fs::remove_dir_all(&dir);
+
+            assert!(result.starts_with(&format!("{} exists but could not be run", link.display())));
+        }
+
+        #[test]
+        fn play_falls_back_to_mpv_when_cvlc_cannot_be_executed() {
+            let _guard = env_lock().lock().expect("env lock");
+            let dir = fake_program_with_mode("fallback", "cvlc", "exit 0", 0o644);
+            let mpv = dir.join("mpv");
+            std::fs::write(&mpv, "#!/bin/sh\nexec /bin/cat > /dev/null\n").expect("write mpv");
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&mpv, std::fs::Permissions::from_mode(0o755))
+                    .expect("make mpv executable");
+            }
+
+            let mut player = Player::new();
+            player.backend = Backend::Vlc;
+            let result = play_with_path(&mut player, &dir);
+            let backend = player.backend;
+            player.stop();
+            let _ = std::fs::remove_dir_all(&dir);
+
+            assert_eq!(result, None);
+            assert_eq!(backend, Backend::Mpv);
+        }
+
+        #[test]
+        fn play_returns_install_hint_when_cvlc_is_not_on_path() {
+            let _guard = env_lock().lock().expect("env lock");
+            let original_path = std::env::var_os("PATH");
+
+            unsafe {
+                std::env::set_var("PATH", "");
+            }
+
+            let mut player = Player::new();
+            let result = player.play("https://example.com/stream");
+
+            match original_path {
+                Some(path) => unsafe { std::env::set_var("PATH", path) },
+                None => unsafe { std::env::remove_var("PATH") },
+            }
+
+            assert_eq!(result, Some(NOT_FOUND_MESSAGE.to_string()));
+            assert!(NOT_FOUND_MESSAGE.contains("cvlc") && NOT_FOUND_MESSAGE.contains("mpv"));
         }
     }
 }
 
 #[cfg(target_os = "windows")]
 mod imp {
+    use super::PlaybackState;
     use windows::{
-        Foundation::Uri,
+        Foundation::{TimeSpan, Uri},
         Media::{Core::MediaSource, Playback::MediaPlayer},
         core::HSTRING,
     };
@@ -198,6 +1488,7 @@ mod imp {
         player: Option<MediaPlayer>,
         pub volume: u8,
         is_playing: bool,
+        paused: bool,
     }
 
     impl Player {
@@ -206,6 +1497,7 @@ mod imp {
                 player: None,
                 volume: 50,
                 is_playing: false,
+                paused: false,
             }
         }
 
@@ -254,6 +1546,7 @@ mod imp {
             }
             self.player = None;
             self.is_playing = false;
+            self.paused = false;
         }
 
         #[allow(dead_code)]
@@ -261,6 +1554,73 @@ mod imp {
             self.is_playing
         }
 
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        pub fn has_exited(&mut self) -> bool {
+            false
+        }
+
+        pub fn state(&self) -> PlaybackState {
+            if self.is_playing {
+                PlaybackState::Playing
+            } else {
+                PlaybackState::Stopped
+            }
+        }
+
+        pub fn start_recording(
+            &mut self,
+            _url: &str,
+            _path: &std::path::Path,
+        ) -> Result<(), String> {
+            Err("Recording is not supported on Windows yet".to_string())
+        }
+
+        pub fn stop_recording(&mut self) -> Option<std::path::PathBuf> {
+            None
+        }
+
+        pub fn is_recording(&mut self) -> bool {
+            false
+        }
+
+        pub fn check_rc_interface(&mut self) -> Option<String> {
+            None
+        }
+
+        pub fn poll_metadata(&mut self) {}
+
+        pub fn now_playing_title(&self) -> Option<String> {
+            None
+        }
+
+        pub fn resume(&mut self) -> bool {
+            let Some(player) = &self.player else {
+                return false;
+            };
+            if !self.paused || player.Play().is_err() {
+                return false;
+            }
+            self.paused = false;
+            true
+        }
+
+        pub fn toggle_pause(&mut self) -> bool {
+            if self.paused {
+                return self.resume();
+            }
+            let Some(player) = &self.player else {
+                return false;
+            };
+            if player.Pause().is_err() {
+                return false;
+            }
+            self.paused = true;
+            true
+        }
+
         pub fn volume_up(&mut self) {
             let next = self.volume.saturating_add(5).min(100);
             self.set_volume(next);
@@ -271,7 +1631,28 @@ mod imp {
             self.set_volume(next);
         }
 
-        fn set_volume(&mut self, percent: u8) {
+        pub fn seek(&mut self, seconds: i64) -> bool {
+            let Some(player) = &self.player else {
+                return false;
+            };
+            let Ok(session) = player.PlaybackSession() else {
+                return false;
+            };
+            if !session.CanSeek().unwrap_or(false) {
+                return false;
+            }
+            let (Ok(position), Ok(length)) = (session.Position(), session.NaturalDuration()) else {
+                return false;
+            };
+            let target = (position.Duration + seconds * 10_000_000).clamp(0, length.Duration);
+            session.SetPosition(TimeSpan { Duration: target }).is_ok()
+        }
+
+        pub fn take_seek_failure(&mut self) -> bool {
+            false
+        }
+
+        pub fn set_volume(&mut self, percent: u8) {
             self.volume = percent.min(100);
             if let Some(player) = &self.player {
                 let _ = player.SetVolume(self.volume as f64 / 100.0);
@@ -324,6 +1705,13 @@ mod imp {
             player.stop();
             assert!(!player.is_playing());
         }
+
+        #[test]
+        fn resume_is_a_no_op_when_not_paused() {
+            let mut player = Player::new();
+            assert!(!player.is_paused());
+            assert!(!player.resume());
+        }
     }
 }
 
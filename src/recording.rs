@@ -0,0 +1,104 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::paths;
+
+fn recordings_dir(custom: Option<&str>) -> Result<PathBuf, String> {
+    if let Some(dir) = custom.map(str::trim).filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    Ok(paths::data_dir()?.join("recordings"))
+}
+
+fn file_extension(codec: &str) -> &'static str {
+    match codec.trim().to_lowercase().as_str() {
+        "mp3" => "mp3",
+        "aac" | "aac+" => "aac",
+        "ogg" | "vorbis" => "ogg",
+        "opus" => "opus",
+        "flac" => "flac",
+        _ => "raw",
+    }
+}
+
+fn safe_file_stem(name: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.trim_matches('_').is_empty() {
+        "station".to_string()
+    } else {
+        stem
+    }
+}
+
+fn recording_file(dir: &Path, station: &str, codec: &str, timestamp: u64) -> PathBuf {
+    dir.join(format!(
+        "{}-{}.{}",
+        safe_file_stem(station),
+        timestamp,
+        file_extension(codec)
+    ))
+}
+
+pub fn new_recording_path(
+    custom_dir: Option<&str>,
+    station: &str,
+    codec: &str,
+) -> Result<PathBuf, String> {
+    let dir = recordings_dir(custom_dir)?;
+    fs::create_dir_all(&dir).map_err(|e| {
+        format!(
+            "Failed to create recordings directory {}: {}",
+            dir.display(),
+            e
+        )
+    })?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    Ok(recording_file(&dir, station, codec, timestamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{file_extension, recording_file, recordings_dir, safe_file_stem};
+    use std::path::Path;
+
+    #[test]
+    fn recording_files_are_named_after_station_time_and_codec() {
+        assert_eq!(
+            recording_file(Path::new("/rec"), "Jazz FM / 24h", "MP3", 1700000000),
+            Path::new("/rec/Jazz_FM___24h-1700000000.mp3")
+        );
+        assert_eq!(
+            recording_file(Path::new("/rec"), "Radio Ö1", "AAC+", 1),
+            Path::new("/rec/Radio_Ö1-1.aac")
+        );
+        assert_eq!(safe_file_stem(" ?? "), "station");
+        assert_eq!(file_extension(""), "raw");
+    }
+
+    #[test]
+    fn recordings_dir_prefers_the_configured_directory() {
+        assert_eq!(
+            recordings_dir(Some("/music/radio")).expect("custom dir"),
+            Path::new("/music/radio")
+        );
+        let default = recordings_dir(Some(" ")).expect("default dir");
+        assert!(default.ends_with("recordings"));
+        assert!(default.to_string_lossy().contains("cradio"));
+    }
+}
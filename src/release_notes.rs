@@ -0,0 +1,58 @@
+const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+fn notes_from_changelog(changelog: &str, version: &str) -> Option<String> {
+    let heading = format!("## [{}]", version);
+    let start = changelog.find(&heading)?;
+    let rest = &changelog[start..];
+    let body = rest.find('\n').map_or("", |end| &rest[end + 1..]);
+    let section = body.find("\n## ").map_or(body, |end| &body[..end]);
+
+    let notes = section.trim();
+    if notes.is_empty() {
+        None
+    } else {
+        Some(notes.to_string())
+    }
+}
+
+pub fn whats_new(last_seen_version: Option<&str>) -> Option<String> {
+    let last_seen = last_seen_version?;
+    if last_seen == CURRENT_VERSION {
+        return None;
+    }
+    notes_from_changelog(CHANGELOG, CURRENT_VERSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CURRENT_VERSION, notes_from_changelog, whats_new};
+
+    const SAMPLE: &str = "# Changelog\n\n## [Unreleased]\n\n- Pending\n\n## [0.2.0] - 2026-01-01\n\n### Added\n\n- Pause support.\n\n## [0.1.0]\n\n- Initial release.\n";
+
+    #[test]
+    fn notes_are_extracted_for_the_requested_version_only() {
+        let notes = notes_from_changelog(SAMPLE, "0.2.0").expect("section exists");
+        assert_eq!(notes, "### Added\n\n- Pause support.");
+
+        let notes = notes_from_changelog(SAMPLE, "0.1.0").expect("last section exists");
+        assert_eq!(notes, "- Initial release.");
+    }
+
+    #[test]
+    fn notes_are_missing_for_unknown_versions() {
+        assert_eq!(notes_from_changelog(SAMPLE, "9.9.9"), None);
+    }
+
+    #[test]
+    fn whats_new_is_skipped_on_first_run_and_for_same_version() {
+        assert_eq!(whats_new(None), None);
+        assert_eq!(whats_new(Some(CURRENT_VERSION)), None);
+    }
+
+    #[test]
+    fn whats_new_is_shown_after_an_upgrade() {
+        assert!(whats_new(Some("0.0.1")).is_some());
+    }
+}
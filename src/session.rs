@@ -0,0 +1,205 @@
+use reqwest::Url;
+
+use crate::{api::SearchParams, app::StationViewMode};
+
+const SESSION_SCHEME: &str = "cradio";
+const SESSION_HOST: &str = "session";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSnapshot {
+    pub name: String,
+    pub tags: String,
+    pub country: String,
+    pub state: String,
+    pub language: String,
+    pub bitrate: Option<u32>,
+    pub bitrate_max: Option<u32>,
+    pub codec: String,
+    pub view_mode: StationViewMode,
+    pub playing_uuid: Option<String>,
+}
+
+impl SessionSnapshot {
+    pub fn from_state(
+        params: &SearchParams,
+        view_mode: &StationViewMode,
+        playing_uuid: Option<&str>,
+    ) -> Self {
+        Self {
+            name: params.name.clone(),
+            tags: params.tags.clone(),
+            country: params.country.clone(),
+            state: params.state.clone(),
+            language: params.language.clone(),
+            bitrate: params.bitrate_min,
+            bitrate_max: params.bitrate_max,
+            codec: params.codec.clone(),
+            view_mode: view_mode.clone(),
+            playing_uuid: playing_uuid.map(str::to_string),
+        }
+    }
+
+    pub fn to_url(&self) -> String {
+        let mut url = Url::parse(&format!("{}://{}", SESSION_SCHEME, SESSION_HOST))
+            .expect("session base URL is valid");
+        {
+            let mut query = url.query_pairs_mut();
+            for (key, value) in [
+                ("name", &self.name),
+                ("tags", &self.tags),
+                ("country", &self.country),
+                ("state", &self.state),
+                ("language", &self.language),
+                ("codec", &self.codec),
+            ] {
+                if !value.is_empty() {
+                    query.append_pair(key, value);
+                }
+            }
+            if let Some(bitrate) = self.bitrate {
+                query.append_pair("bitrate", &bitrate.to_string());
+            }
+            if let Some(bitrate) = self.bitrate_max {
+                query.append_pair("bitrate_max", &bitrate.to_string());
+            }
+            if self.view_mode == StationViewMode::Favorites {
+                query.append_pair("view", "favorites");
+            }
+            if let Some(uuid) = &self.playing_uuid {
+                query.append_pair("play", uuid);
+            }
+        }
+        if url.query() == Some("") {
+            url.set_query(None);
+        }
+        url.to_string()
+    }
+
+    pub fn from_url(input: &str) -> Result<Self, String> {
+        let url = Url::parse(input.trim()).map_err(|e| format!("Invalid session link: {}", e))?;
+        if url.scheme() != SESSION_SCHEME || url.host_str() != Some(SESSION_HOST) {
+            return Err(format!(
+                "Invalid session link: expected {}://{}",
+                SESSION_SCHEME, SESSION_HOST
+            ));
+        }
+
+        let mut snapshot = Self {
+            name: String::new(),
+            tags: String::new(),
+            country: String::new(),
+            state: String::new(),
+            language: String::new(),
+            bitrate: None,
+            bitrate_max: None,
+            codec: String::new(),
+            view_mode: StationViewMode::AllStations,
+            playing_uuid: None,
+        };
+
+        for (key, value) in url.query_pairs() {
+            let value = value.trim().to_string();
+            match key.as_ref() {
+                "name" => snapshot.name = value,
+                "tags" => snapshot.tags = value,
+                "country" => snapshot.country = value.to_uppercase(),
+                "state" => snapshot.state = value,
+                "language" => snapshot.language = value.to_lowercase(),
+                "codec" => snapshot.codec = value.to_uppercase(),
+                "bitrate" => snapshot.bitrate = Some(parse_bitrate(&value)?),
+                "bitrate_max" => snapshot.bitrate_max = Some(parse_bitrate(&value)?),
+                "view" => {
+                    snapshot.view_mode = match value.as_str() {
+                        "favorites" => StationViewMode::Favorites,
+                        "all" => StationViewMode::AllStations,
+                        other => return Err(format!("Invalid session view: {}", other)),
+                    }
+                }
+                "play" if !value.is_empty() => snapshot.playing_uuid = Some(value),
+                _ => {}
+            }
+        }
+
+        if let (Some(min), Some(max)) = (snapshot.bitrate, snapshot.bitrate_max)
+            && min > max
+        {
+            return Err(format!(
+                "Invalid session bitrate range: {} is above {}",
+                min, max
+            ));
+        }
+
+        Ok(snapshot)
+    }
+}
+
+fn parse_bitrate(value: &str) -> Result<u32, String> {
+    value
+        .parse::<u32>()
+        .map_err(|_| format!("Invalid session bitrate: {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SessionSnapshot;
+    use crate::{api::SearchParams, app::StationViewMode};
+
+    #[test]
+    fn session_url_roundtrips_filters_view_and_playing_station() {
+        let params = SearchParams {
+            name: "Jazz & Blues FM".to_string(),
+            tags: "jazz,blues".to_string(),
+            country: "DE".to_string(),
+            state: "Bavaria".to_string(),
+            language: "de".to_string(),
+            bitrate_min: Some(128),
+            bitrate_max: Some(320),
+            codec: "AAC".to_string(),
+            ..SearchParams::default()
+        };
+        let snapshot =
+            SessionSnapshot::from_state(&params, &StationViewMode::Favorites, Some("uuid-1"));
+
+        let url = snapshot.to_url();
+        assert!(url.starts_with("cradio://session?"));
+
+        let restored = SessionSnapshot::from_url(&url).expect("roundtrip should parse");
+        assert_eq!(restored, snapshot);
+    }
+
+    #[test]
+    fn session_url_omits_empty_fields() {
+        let snapshot = SessionSnapshot::from_state(
+            &SearchParams::default(),
+            &StationViewMode::AllStations,
+            None,
+        );
+
+        assert_eq!(snapshot.to_url(), "cradio://session");
+        let restored = SessionSnapshot::from_url("cradio://session").expect("bare link parses");
+        assert_eq!(restored, snapshot);
+    }
+
+    #[test]
+    fn session_url_rejects_foreign_links() {
+        let err = SessionSnapshot::from_url("https://example.com/?name=x")
+            .expect_err("foreign scheme should fail");
+        assert!(err.contains("expected cradio://session"));
+
+        let err = SessionSnapshot::from_url("cradio://session?bitrate=fast")
+            .expect_err("non-numeric bitrate should fail");
+        assert!(err.contains("Invalid session bitrate"));
+
+        let err = SessionSnapshot::from_url("cradio://session?bitrate=320&bitrate_max=128")
+            .expect_err("inverted bitrate range should fail");
+        assert!(err.contains("Invalid session bitrate range"));
+    }
+
+    #[test]
+    fn session_url_normalizes_country_and_language_case() {
+        let restored = SessionSnapshot::from_url("cradio://session?country=us&language=EN")
+            .expect("link parses");
+        assert_eq!(restored.country, "US");
+        assert_eq!(restored.language, "en");
+    }
+}
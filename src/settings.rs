@@ -0,0 +1,322 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    favorites::{TextSeparator, TieBreak},
+    paths,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    Default,
+    Mono,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmQuit {
+    #[default]
+    Never,
+    Playing,
+    Always,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedSearch {
+    pub name: String,
+    pub tags: String,
+    pub country: String,
+    pub state: String,
+    pub language: String,
+    pub bitrate: Option<u32>,
+    pub bitrate_max: Option<u32>,
+    pub codec: String,
+}
+
+pub const DEFAULT_COLUMNS: [&str; 6] = ["name", "country", "language", "tags", "bitrate", "codec"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub last_seen_version: Option<String>,
+    pub notifications: bool,
+    pub confirm_quit: ConfirmQuit,
+    pub columns: Vec<String>,
+    pub compact_filters_below: u16,
+    pub custom_directory: Option<String>,
+    pub default_country: Option<String>,
+    pub default_language: Option<String>,
+    pub saved_searches: BTreeMap<String, SavedSearch>,
+    pub startup_search: Option<String>,
+    pub check_reachability: bool,
+    pub auto_search: bool,
+    pub exact_tags: bool,
+    pub tick_rate_ms: u64,
+    pub nav_repeat_ms: u64,
+    pub show_range: bool,
+    pub terminal_title: bool,
+    pub mini_layout: bool,
+    pub theme: Theme,
+    pub hide_na: bool,
+    pub keys: BTreeMap<String, String>,
+    pub max_favorites: Option<usize>,
+    pub volume_presets: Vec<u8>,
+    pub favorites_first: bool,
+    pub favorites_tie_break: TieBreak,
+    pub export_separator: TextSeparator,
+    pub external_player: Option<String>,
+    pub audio_device: Option<String>,
+    pub prefer_resolved: bool,
+    pub quit_grace_ms: Option<u64>,
+    pub stream_output: Option<String>,
+    pub recordings_dir: Option<String>,
+    pub external_keep_playing: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            last_seen_version: None,
+            notifications: false,
+            confirm_quit: ConfirmQuit::Never,
+            columns: DEFAULT_COLUMNS.iter().map(|c| c.to_string()).collect(),
+            compact_filters_below: 80,
+            custom_directory: None,
+            default_country: None,
+            default_language: None,
+            saved_searches: BTreeMap::new(),
+            startup_search: None,
+            check_reachability: false,
+            auto_search: true,
+            exact_tags: true,
+            tick_rate_ms: 200,
+            nav_repeat_ms: 0,
+            show_range: false,
+            terminal_title: false,
+            mini_layout: false,
+            theme: Theme::Default,
+            hide_na: false,
+            keys: BTreeMap::new(),
+            max_favorites: None,
+            volume_presets: vec![20, 40, 60, 80, 100],
+            favorites_first: false,
+            favorites_tie_break: TieBreak::Uuid,
+            export_separator: TextSeparator::Newline,
+            external_player: None,
+            audio_device: None,
+            prefer_resolved: true,
+            quit_grace_ms: None,
+            stream_output: None,
+            recordings_dir: None,
+            external_keep_playing: false,
+        }
+    }
+}
+
+impl Settings {
+    pub fn tick_rate(&self) -> Duration {
+        Duration::from_millis(self.tick_rate_ms.clamp(20, 5000))
+    }
+
+    pub fn nav_repeat(&self) -> Duration {
+        Duration::from_millis(self.nav_repeat_ms.min(1000))
+    }
+
+    pub fn quit_grace(&self) -> Option<Duration> {
+        self.quit_grace_ms
+            .map(|ms| Duration::from_millis(ms.min(5000)))
+    }
+}
+
+fn settings_path() -> Result<PathBuf, String> {
+    Ok(paths::config_dir()?.join("settings.json"))
+}
+
+fn load_settings_from_path(path: &Path) -> Result<Settings, String> {
+    if !path.exists() {
+        return Ok(Settings::default());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read settings file {}: {}", path.display(), e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse settings JSON {}: {}", path.display(), e))
+}
+
+fn save_settings_to_path(path: &Path, settings: &Settings) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            format!(
+                "Failed to create settings directory {}: {}",
+                parent.display(),
+                e
+            )
+        })?;
+    }
+
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+    fs::write(path, json)
+        .map_err(|e| format!("Failed to write settings file {}: {}", path.display(), e))
+}
+
+pub fn load_settings() -> Result<Settings, String> {
+    let path = settings_path()?;
+    load_settings_from_path(&path)
+}
+
+pub fn save_settings(settings: &Settings) -> Result<(), String> {
+    let path = settings_path()?;
+    save_settings_to_path(&path, settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ConfirmQuit, SavedSearch, Settings, TextSeparator, Theme, TieBreak,
+        load_settings_from_path, save_settings_to_path, settings_path,
+    };
+    use std::{
+        collections::BTreeMap,
+        fs,
+        path::{Path, PathBuf},
+        sync::atomic::{AtomicU64, Ordering},
+        time::Duration,
+    };
+
+    static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn temp_path(name: &str) -> PathBuf {
+        let id = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir()
+            .join(format!(
+                "cradio-settings-test-{}-{}-{}",
+                name,
+                std::process::id(),
+                id
+            ))
+            .join("settings.json")
+    }
+
+    fn cleanup(path: &Path) {
+        if let Some(parent) = path.parent() {
+            let _ = fs::remove_dir_all(parent);
+        }
+    }
+
+    #[test]
+    fn load_missing_file_returns_defaults() {
+        let path = temp_path("missing");
+        let settings = load_settings_from_path(&path).expect("load should succeed");
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn save_and_load_roundtrip() {
+        let path = temp_path("roundtrip");
+        let settings = Settings {
+            last_seen_version: Some("0.1.0".to_string()),
+            notifications: true,
+            confirm_quit: ConfirmQuit::Playing,
+            columns: vec!["name".to_string(), "votes".to_string()],
+            compact_filters_below: 100,
+            custom_directory: Some("https://radio.example/search".to_string()),
+            default_country: Some("DE".to_string()),
+            default_language: Some("german".to_string()),
+            saved_searches: BTreeMap::from([(
+                "jazz".to_string(),
+                SavedSearch {
+                    tags: "jazz".to_string(),
+                    bitrate: Some(128),
+                    bitrate_max: Some(320),
+                    codec: "AAC".to_string(),
+                    ..SavedSearch::default()
+                },
+            )]),
+            startup_search: Some("jazz".to_string()),
+            check_reachability: true,
+            auto_search: false,
+            exact_tags: false,
+            tick_rate_ms: 500,
+            nav_repeat_ms: 40,
+            show_range: true,
+            terminal_title: true,
+            mini_layout: true,
+            theme: Theme::Mono,
+            hide_na: true,
+            keys: BTreeMap::from([("stop".to_string(), "k".to_string())]),
+            max_favorites: Some(200),
+            volume_presets: vec![10, 90],
+            favorites_first: true,
+            favorites_tie_break: TieBreak::Url,
+            export_separator: TextSeparator::Comma,
+            external_player: Some("mpv --force-window".to_string()),
+            audio_device: Some("alsa_output.usb-headset".to_string()),
+            prefer_resolved: false,
+            quit_grace_ms: Some(300),
+            stream_output: Some("/tmp/cradio.fifo".to_string()),
+            recordings_dir: Some("/music/radio".to_string()),
+            external_keep_playing: true,
+        };
+
+        save_settings_to_path(&path, &settings).expect("save should work");
+        let loaded = load_settings_from_path(&path).expect("load should work");
+
+        assert_eq!(loaded, settings);
+        cleanup(&path);
+    }
+
+    #[test]
+    fn load_fills_missing_fields_with_defaults() {
+        let path = temp_path("partial");
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        fs::write(&path, "{}").expect("write settings");
+
+        let loaded = load_settings_from_path(&path).expect("load should work");
+
+        assert_eq!(loaded, Settings::default());
+        cleanup(&path);
+    }
+
+    #[test]
+    fn tick_rate_is_clamped_to_a_sane_range() {
+        let mut settings = Settings::default();
+        assert_eq!(settings.tick_rate(), Duration::from_millis(200));
+
+        settings.tick_rate_ms = 0;
+        assert_eq!(settings.tick_rate(), Duration::from_millis(20));
+
+        settings.tick_rate_ms = 60_000;
+        assert_eq!(settings.tick_rate(), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn load_invalid_json_returns_error() {
+        let path = temp_path("invalid");
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        fs::write(&path, "{not-json]").expect("write invalid json");
+
+        let err = load_settings_from_path(&path).expect_err("expected parse error");
+        assert!(err.contains("Failed to parse settings JSON"));
+
+        cleanup(&path);
+    }
+
+    #[test]
+    fn settings_path_uses_native_config_directory() {
+        let path = settings_path().expect("native config path should resolve");
+        assert!(path.ends_with("settings.json"));
+        assert!(path.to_string_lossy().contains("cradio"));
+    }
+}
@@ -0,0 +1,77 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{json_file, paths};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct State {
+    pub volume: u8,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self { volume: 50 }
+    }
+}
+
+fn state_path() -> Result<PathBuf, String> {
+    Ok(paths::config_dir()?.join("state.json"))
+}
+
+fn load_state_from_path(path: &Path) -> State {
+    let mut state: State = json_file::load(path, "state")
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    state.volume = state.volume.min(100);
+    state
+}
+
+pub fn load_state() -> State {
+    state_path()
+        .map(|path| load_state_from_path(&path))
+        .unwrap_or_default()
+}
+
+pub fn save_state(state: &State) -> Result<(), String> {
+    json_file::save(&state_path()?, state, "state")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{State, load_state_from_path};
+    use crate::json_file;
+    use std::{fs, path::PathBuf};
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("cradio-state-test-{}-{}", name, std::process::id()))
+            .join("state.json")
+    }
+
+    #[test]
+    fn volume_roundtrips_and_defaults_to_fifty() {
+        let path = temp_path("roundtrip");
+        assert_eq!(load_state_from_path(&path).volume, 50);
+
+        json_file::save(&path, &State { volume: 20 }, "state").expect("save should work");
+
+        assert_eq!(load_state_from_path(&path), State { volume: 20 });
+        let _ = fs::remove_dir_all(path.parent().expect("parent"));
+    }
+
+    #[test]
+    fn corrupt_or_out_of_range_state_is_ignored() {
+        let path = temp_path("corrupt");
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+
+        fs::write(&path, "{\"volume\":").expect("write state");
+        assert_eq!(load_state_from_path(&path), State::default());
+
+        fs::write(&path, "{\"volume\":250}").expect("write state");
+        assert_eq!(load_state_from_path(&path).volume, 100);
+        let _ = fs::remove_dir_all(path.parent().expect("parent"));
+    }
+}
@@ -0,0 +1,66 @@
+use std::{cmp::Reverse, collections::BTreeMap, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{json_file, paths};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StationStats {
+    pub plays: u32,
+    pub last_played: u64,
+}
+
+pub type PlayStats = BTreeMap<String, StationStats>;
+
+pub fn record_play(stats: &mut PlayStats, uuid: &str, now: u64) {
+    let entry = stats.entry(uuid.to_string()).or_default();
+    entry.plays = entry.plays.saturating_add(1);
+    entry.last_played = now;
+}
+
+pub fn most_played(stats: &PlayStats, limit: usize) -> Vec<String> {
+    let mut ranked: Vec<(&String, &StationStats)> =
+        stats.iter().filter(|(_, entry)| entry.plays > 0).collect();
+    ranked.sort_by_key(|(uuid, entry)| (Reverse(entry.plays), Reverse(entry.last_played), *uuid));
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(uuid, _)| uuid.clone())
+        .collect()
+}
+
+fn stats_path() -> Result<PathBuf, String> {
+    Ok(paths::config_dir()?.join("stats.json"))
+}
+
+pub fn load_stats() -> Result<PlayStats, String> {
+    Ok(json_file::load(&stats_path()?, "stats")?.unwrap_or_default())
+}
+
+pub fn save_stats(stats: &PlayStats) -> Result<(), String> {
+    json_file::save(&stats_path()?, stats, "stats")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PlayStats, StationStats, most_played, record_play};
+
+    #[test]
+    fn plays_are_counted_and_ranked_by_count_then_recency() {
+        let mut stats = PlayStats::new();
+        record_play(&mut stats, "jazz", 10);
+        record_play(&mut stats, "news", 20);
+        record_play(&mut stats, "rock", 30);
+        record_play(&mut stats, "jazz", 40);
+
+        assert_eq!(
+            stats["jazz"],
+            StationStats {
+                plays: 2,
+                last_played: 40
+            }
+        );
+        assert_eq!(most_played(&stats, 10), vec!["jazz", "rock", "news"]);
+        assert_eq!(most_played(&stats, 1), vec!["jazz"]);
+    }
+}
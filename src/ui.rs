@@ -1,18 +1,150 @@
+use std::ops::Range;
+
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    widgets::{
+        Block, Borders, Cell, Clear, Paragraph, Row, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Table, TableState, Wrap,
+    },
 };
 
-use crate::app::{App, AppMode, InputField, StationViewMode};
+use crate::{
+    api::Station,
+    app::{App, AppMode, InputField, StationViewMode, quick_filter_match},
+    keymap::Action,
+    player::PlaybackState,
+    settings::DEFAULT_COLUMNS,
+};
 
 const NEON_CYAN: Color = Color::Cyan;
 const NEON_MAGENTA: Color = Color::Magenta;
 const SELECTED_BG: Color = Color::Rgb(40, 0, 60);
+const NOT_AVAILABLE: &str = "N/A";
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Name,
+    Country,
+    Language,
+    Tags,
+    Bitrate,
+    Votes,
+    Codec,
+    Homepage,
+}
+
+impl Column {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "name" => Some(Self::Name),
+            "country" => Some(Self::Country),
+            "language" => Some(Self::Language),
+            "tags" => Some(Self::Tags),
+            "bitrate" => Some(Self::Bitrate),
+            "votes" => Some(Self::Votes),
+            "codec" => Some(Self::Codec),
+            "homepage" => Some(Self::Homepage),
+            _ => None,
+        }
+    }
+
+    fn header(self) -> &'static str {
+        match self {
+            Self::Name => "Name",
+            Self::Country => "Country",
+            Self::Language => "Language",
+            Self::Tags => "Tags",
+            Self::Bitrate => "Bitrate",
+            Self::Votes => "Votes",
+            Self::Codec => "Codec",
+            Self::Homepage => "Homepage",
+        }
+    }
+
+    fn constraint(self) -> Constraint {
+        match self {
+            Self::Name | Self::Homepage => Constraint::Min(20),
+            Self::Tags => Constraint::Min(14),
+            Self::Country => Constraint::Length(8),
+            Self::Language => Constraint::Length(12),
+            Self::Bitrate => Constraint::Length(9),
+            Self::Votes => Constraint::Length(7),
+            Self::Codec => Constraint::Length(6),
+        }
+    }
+
+    fn cell_text(self, station: &Station, placeholder: &str) -> String {
+        match self {
+            Self::Name => truncate(&station.name, 32),
+            Self::Country => display_country(station, placeholder),
+            Self::Language => display_language(station, placeholder),
+            Self::Tags => display_tags(station, 30),
+            Self::Bitrate => display_bitrate(station, placeholder),
+            Self::Votes => station.votes.to_string(),
+            Self::Codec => display_or_na(&station.codec, placeholder),
+            Self::Homepage => truncate(&station.homepage, 40),
+        }
+    }
+}
+
+fn visible_columns(app: &App) -> Vec<Column> {
+    let mut columns: Vec<Column> = Vec::new();
+    for column in app
+        .settings
+        .columns
+        .iter()
+        .filter_map(|name| Column::from_name(name))
+    {
+        if !columns.contains(&column) {
+            columns.push(column);
+        }
+    }
+    if columns.is_empty() {
+        columns = DEFAULT_COLUMNS
+            .iter()
+            .filter_map(|name| Column::from_name(name))
+            .collect();
+    }
+    columns
+}
 
 pub fn draw(frame: &mut Frame, app: &App, table_state: &mut TableState) {
+    if app.mini {
+        draw_mini(frame, app, frame.area());
+    } else {
+        draw_full(frame, app, table_state);
+    }
+    if app.mode == AppMode::Help {
+        draw_help(frame, app, frame.area());
+    }
+    if app.monochrome {
+        strip_colors(frame.buffer_mut());
+    }
+}
+
+pub fn monochrome_from_env(no_color: Option<&str>, term: Option<&str>) -> bool {
+    no_color.is_some_and(|value| !value.is_empty()) || term == Some("dumb")
+}
+
+fn strip_colors(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if cell.bg == SELECTED_BG {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        if cell.fg == Color::Green {
+            cell.modifier.insert(Modifier::BOLD);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
+
+fn draw_full(frame: &mut Frame, app: &App, table_state: &mut TableState) {
     let size = frame.area();
 
     let chunks = Layout::default()
@@ -26,15 +158,341 @@ pub fn draw(frame: &mut Frame, app: &App, table_state: &mut TableState) {
         ])
         .split(size);
 
-    draw_header(frame, chunks[0]);
+    draw_header(frame, app, chunks[0]);
     draw_now_playing(frame, app, chunks[1]);
     draw_filters(frame, app, chunks[2]);
     draw_station_list(frame, app, table_state, chunks[3]);
     draw_footer(frame, app, chunks[4]);
+
+    if app.show_details
+        && let Some(station) = app.selected_station()
+    {
+        draw_details(frame, app, station, size);
+    }
+
+    if let Some(notes) = &app.whats_new {
+        draw_whats_new(frame, notes, size);
+    }
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+
+fn draw_mini(frame: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Length(1)])
+        .split(area);
+
+    draw_now_playing(frame, app, chunks[0]);
+
+    let mut spans = vec![Span::styled(
+        format!("Vol: {}%", app.volume_display()),
+        Style::default().fg(NEON_CYAN),
+    )];
+    spans.push(Span::styled("  │  ", Style::default().fg(Color::DarkGray)));
+    match &app.status {
+        Some(status) => spans.push(Span::styled(
+            status.as_str(),
+            Style::default().fg(Color::Yellow),
+        )),
+        None => spans.push(Span::styled(
+            format!("{} Full view", app.keymap.label(Action::Mini)),
+            Style::default().fg(Color::Gray),
+        )),
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), chunks[1]);
+}
+
+fn draw_details(frame: &mut Frame, app: &App, station: &Station, area: Rect) {
+    let popup_area = centered_rect(70, 60, area);
+    let label = |text: &'static str| {
+        Span::styled(
+            format!("{:<10}", text),
+            Style::default().fg(NEON_CYAN).add_modifier(Modifier::BOLD),
+        )
+    };
+    let value = |text: String| Span::styled(text, Style::default().fg(Color::White));
+
+    let lines = vec![
+        Line::from(vec![label("Name"), value(station.name.clone())]),
+        Line::from(vec![
+            label("Country"),
+            value(display_country(station, NOT_AVAILABLE)),
+        ]),
+        Line::from(vec![
+            label("Language"),
+            value(display_or_na(&station.language, NOT_AVAILABLE)),
+        ]),
+        Line::from(vec![
+            label("Tags"),
+            value(display_or_na(&station.tags, NOT_AVAILABLE)),
+        ]),
+        Line::from(vec![
+            label("Bitrate"),
+            value(display_bitrate(station, NOT_AVAILABLE)),
+        ]),
+        Line::from(vec![label("URL"), value(station.url.clone())]),
+        Line::from(vec![
+            label("Favicon"),
+            value(display_or_na(&station.favicon, NOT_AVAILABLE)),
+        ]),
+        Line::from(vec![
+            label("Plays"),
+            value(format!(
+                "{} this session, {} total",
+                app.play_count(&station.stationuuid),
+                app.total_play_count(&station.stationuuid)
+            )),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Press i or Esc to close",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    let block = Block::default()
+        .title(Span::styled(
+            " Station Details ",
+            Style::default().fg(NEON_CYAN).add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(NEON_MAGENTA));
+    let inner = block.inner(popup_area);
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(block, popup_area);
+
+    let text_area = draw_favicon(frame, app, station, inner);
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), text_area);
+}
+
+#[cfg(feature = "favicons")]
+fn draw_favicon(frame: &mut Frame, app: &App, station: &Station, area: Rect) -> Rect {
+    use crate::favicon::FAVICON_SIZE;
+    use ratatui_image::Image;
+
+    let Some((_, protocol)) = app
+        .favicon
+        .as_ref()
+        .filter(|(uuid, _)| *uuid == station.stationuuid)
+    else {
+        return area;
+    };
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(FAVICON_SIZE.width + 2),
+            Constraint::Min(0),
+        ])
+        .split(area);
+    frame.render_widget(Image::new(protocol), columns[0]);
+    columns[1]
+}
+
+#[cfg(not(feature = "favicons"))]
+fn draw_favicon(_frame: &mut Frame, _app: &App, _station: &Station, area: Rect) -> Rect {
+    area
+}
+
+fn draw_whats_new(frame: &mut Frame, notes: &str, area: Rect) {
+    let popup_area = centered_rect(70, 60, area);
+    let mut lines: Vec<Line> = notes
+        .lines()
+        .map(|line| {
+            if line.starts_with('#') {
+                Line::from(Span::styled(
+                    line.trim_start_matches('#').trim().to_string(),
+                    Style::default()
+                        .fg(NEON_MAGENTA)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(Color::White),
+                ))
+            }
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Press any key to continue",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let popup = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(Span::styled(
+                format!(" What's new in v{} ", env!("CARGO_PKG_VERSION")),
+                Style::default().fg(NEON_CYAN).add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(NEON_MAGENTA)),
+    );
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(popup, popup_area);
+}
+
+fn help_sections(app: &App) -> Vec<(&'static str, Vec<(String, &'static str)>)> {
+    let keymap = &app.keymap;
+    let pair = |a: Action, b: Action| format!("{}/{}", keymap.label(a), keymap.label(b));
+    vec![
+        (
+            "Navigation",
+            vec![
+                key("↑↓", "Move the selection"),
+                key(
+                    pair(Action::NextPage, Action::PrevPage),
+                    "Next/previous page",
+                ),
+                key(keymap.label(Action::Reverse), "Reverse the result order"),
+                key(
+                    keymap.label(Action::Sort),
+                    "Sort by name, bitrate or country",
+                ),
+                key(keymap.label(Action::Codec), "Only MP3, AAC or OGG stations"),
+                key(
+                    keymap.label(Action::ToggleRange),
+                    "Page number or result range",
+                ),
+                key(keymap.label(Action::Details), "Station details"),
+                key(keymap.label(Action::Mini), "Mini player layout"),
+            ],
+        ),
+        (
+            "Playback",
+            vec![
+                key("Enter", "Play the selected station"),
+                key(keymap.label(Action::Stop), "Stop"),
+                key(keymap.label(Action::Pause), "Pause/continue"),
+                key(keymap.label(Action::Reconnect), "Reconnect"),
+                key(keymap.label(Action::Forget), "Forget the current station"),
+                key(pair(Action::VolumeUp, Action::VolumeDown), "Volume up/down"),
+                key("F1-F5", "Volume presets"),
+                key("←→", "Seek on on-demand streams"),
+                key(keymap.label(Action::Record), "Start/stop recording"),
+                key(keymap.label(Action::External), "Open in an external player"),
+                key(
+                    keymap.label(Action::PreferResolved),
+                    "Prefer resolved or original URL",
+                ),
+                key(keymap.label(Action::Share), "Copy a share link"),
+                key(keymap.label(Action::CopyTrack), "Copy the playing track"),
+            ],
+        ),
+        (
+            "Favorites",
+            vec![
+                key(
+                    keymap.label(Action::Favorite),
+                    "Add/remove the selected station",
+                ),
+                key("1-9", "Add/remove the Nth visible station"),
+                key(keymap.label(Action::Favorites), "Toggle the favorites view"),
+                key(
+                    keymap.label(Action::MostPlayed),
+                    "Toggle the most played view",
+                ),
+                key(keymap.label(Action::History), "Toggle the playback history"),
+                key(keymap.label(Action::RefreshFavorites), "Refresh favorites"),
+                key(
+                    keymap.label(Action::CachedFavorites),
+                    "Show cached favorites",
+                ),
+                key(
+                    keymap.label(Action::UpdateFavorites),
+                    "Apply upstream changes",
+                ),
+                key(keymap.label(Action::FindSimilar), "Find similar stations"),
+                key(keymap.label(Action::Vote), "Vote for the selected station"),
+            ],
+        ),
+        (
+            "Filters",
+            vec![
+                key(keymap.label(Action::Filter), "Edit filters"),
+                key(
+                    keymap.label(Action::QuickFilter),
+                    "Filter loaded stations by name",
+                ),
+                key("Tab", "Next filter field"),
+                key("Enter", "Apply filters and search"),
+                key("Esc", "Leave filter mode"),
+            ],
+        ),
+        (
+            "Other",
+            vec![
+                key(keymap.label(Action::Command), "Command line"),
+                key("Ctrl+R", "Reload settings"),
+                key(keymap.label(Action::Help), "Show/hide this help"),
+                key(keymap.label(Action::Quit), "Quit"),
+            ],
+        ),
+    ]
+}
+
+fn draw_help(frame: &mut Frame, app: &App, area: Rect) {
+    let popup_area = centered_rect(70, 90, area);
+    let mut lines: Vec<Line> = Vec::new();
+    for (title, keys) in help_sections(app) {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            title,
+            Style::default()
+                .fg(NEON_MAGENTA)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (k, desc) in keys {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:>8}", k),
+                    Style::default().fg(NEON_CYAN).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("  {}", desc), Style::default().fg(Color::White)),
+            ]));
+        }
+    }
+
+    let popup = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(Span::styled(
+                format!(
+                    " Help - {} or Esc to close ",
+                    app.keymap.label(Action::Help)
+                ),
+                Style::default().fg(NEON_CYAN).add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(NEON_MAGENTA)),
+    );
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(popup, popup_area);
 }
 
-fn draw_header(frame: &mut Frame, area: Rect) {
-    let title = Paragraph::new(Line::from(vec![
+fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
+    let mut spans = vec![
         Span::styled("🎵 ", Style::default().fg(NEON_CYAN)),
         Span::styled(
             "cradio",
@@ -46,18 +504,37 @@ fn draw_header(frame: &mut Frame, area: Rect) {
             concat!(" — Internet Radio — v", env!("CARGO_PKG_VERSION")),
             Style::default().fg(NEON_CYAN).add_modifier(Modifier::BOLD),
         ),
-    ]))
-    .alignment(Alignment::Center)
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(NEON_MAGENTA)),
-    );
+    ];
+    if app.requests_in_flight > 0 {
+        spans.push(Span::styled(
+            format!(" {}", SPINNER[app.spinner_frame % SPINNER.len()]),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    let title = Paragraph::new(Line::from(spans))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(NEON_MAGENTA)),
+        );
     frame.render_widget(title, area);
 }
 
 fn draw_now_playing(frame: &mut Frame, app: &App, area: Rect) {
-    let content = if let Some(err) = app.now_playing_error() {
+    let content = if let Some(station) = &app.checking_station {
+        Line::from(vec![
+            Span::styled(
+                format!("{} ", SPINNER[app.spinner_frame % SPINNER.len()]),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled("Checking… ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                truncate(&station.name, 40),
+                Style::default().fg(Color::White),
+            ),
+        ])
+    } else if let Some(err) = app.now_playing_error() {
         Line::from(vec![
             Span::styled(
                 "Playback failed: ",
@@ -65,14 +542,31 @@ fn draw_now_playing(frame: &mut Frame, app: &App, area: Rect) {
             ),
             Span::styled(err, Style::default().fg(Color::White)),
         ])
-    } else if let Some(station) = &app.current_station {
-        let country = display_country(station);
-        let language = display_language(station);
-        let tags = display_tags(station, 24);
-        let bitrate = display_bitrate(station);
+    } else if let Some(station) = &app.current_station
+        && app.playback_state == PlaybackState::Connecting
+    {
         Line::from(vec![
             Span::styled(
-                "▶ ",
+                format!("{} ", SPINNER[app.spinner_frame % SPINNER.len()]),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled("Connecting… ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                truncate(&station.name, 40),
+                Style::default().fg(Color::White),
+            ),
+        ])
+    } else if let Some(station) = &app.current_station {
+        let placeholder = missing_placeholder(app);
+        let fields = [
+            (display_country(station, placeholder), NEON_CYAN),
+            (display_language(station, placeholder), Color::White),
+            (display_tags(station, 24), NEON_MAGENTA),
+            (display_bitrate(station, placeholder), NEON_CYAN),
+        ];
+        let mut spans = vec![
+            Span::styled(
+                if app.paused { "⏸ " } else { "▶ " },
                 Style::default()
                     .fg(Color::Green)
                     .add_modifier(Modifier::BOLD),
@@ -83,15 +577,32 @@ fn draw_now_playing(frame: &mut Frame, app: &App, area: Rect) {
                     .fg(Color::White)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(" | ", Style::default().fg(Color::DarkGray)),
-            Span::styled(country, Style::default().fg(NEON_CYAN)),
-            Span::styled(" | ", Style::default().fg(Color::DarkGray)),
-            Span::styled(language, Style::default().fg(Color::White)),
-            Span::styled(" | ", Style::default().fg(Color::DarkGray)),
-            Span::styled(tags, Style::default().fg(NEON_MAGENTA)),
-            Span::styled(" | ", Style::default().fg(Color::DarkGray)),
-            Span::styled(bitrate, Style::default().fg(NEON_CYAN)),
-        ])
+        ];
+        if let Some(track) = app
+            .current_track
+            .as_deref()
+            .map(str::trim)
+            .filter(|track| !track.is_empty())
+        {
+            spans.push(Span::styled(
+                format!(" — {}", truncate(track, 60)),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        for (text, color) in fields {
+            if app.settings.hide_na && text.is_empty() {
+                continue;
+            }
+            spans.push(Span::styled(" | ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(text, Style::default().fg(color)));
+        }
+        if app.recording.is_some() {
+            spans.push(Span::styled(
+                "  ● REC",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+        Line::from(spans)
     } else {
         Line::from(vec![Span::styled(
             "No station playing",
@@ -113,25 +624,42 @@ fn draw_now_playing(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn draw_filters(frame: &mut Frame, app: &App, area: Rect) {
-    let filter_layout = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(28),
-            Constraint::Percentage(14),
-            Constraint::Percentage(14),
-            Constraint::Percentage(30),
-            Constraint::Percentage(14),
-        ])
-        .split(area);
-
     let fields = [
         ("Name", &app.draft_name, InputField::Name),
         ("Country", &app.draft_country, InputField::Country),
+        ("State", &app.draft_state, InputField::State),
         ("Lang", &app.draft_language, InputField::Language),
-        ("Tags", &app.draft_tags, InputField::Tags),
-        ("Bitrate", &app.draft_bitrate, InputField::Bitrate),
+        (
+            if app.settings.exact_tags {
+                "Tags (exact)"
+            } else {
+                "Tags (partial)"
+            },
+            &app.draft_tags,
+            InputField::Tags,
+        ),
+        ("Min kbps", &app.draft_bitrate, InputField::Bitrate),
+        ("Max kbps", &app.draft_bitrate_max, InputField::BitrateMax),
     ];
 
+    if area.width < app.settings.compact_filters_below {
+        draw_compact_filters(frame, app, &fields, area);
+        return;
+    }
+
+    let filter_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(20),
+            Constraint::Percentage(11),
+            Constraint::Percentage(13),
+            Constraint::Percentage(11),
+            Constraint::Percentage(23),
+            Constraint::Percentage(11),
+            Constraint::Percentage(11),
+        ])
+        .split(area);
+
     for (i, (label, value, field)) in fields.iter().enumerate() {
         let is_active = matches!(&app.mode, AppMode::Filtering(f) if f == field);
         let border_style = if is_active {
@@ -164,25 +692,98 @@ fn draw_filters(frame: &mut Frame, app: &App, area: Rect) {
     }
 }
 
-fn draw_station_list(frame: &mut Frame, app: &App, table_state: &mut TableState, area: Rect) {
-    let header_cells = ["Name", "Country", "Language", "Tags", "Bitrate"]
+fn draw_compact_filters(
+    frame: &mut Frame,
+    app: &App,
+    fields: &[(&str, &String, InputField)],
+    area: Rect,
+) {
+    let active = fields
         .iter()
-        .map(|h| {
-            Cell::from(*h).style(
-                Style::default()
-                    .fg(NEON_CYAN)
-                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        .enumerate()
+        .find(|(_, (_, _, field))| matches!(&app.mode, AppMode::Filtering(f) if f == field));
+
+    let (title, content, border_style) = match active {
+        Some((i, (label, value, _))) => (
+            format!(" {} ({}/{}) ", label, i + 1, fields.len()),
+            Span::styled(format!("{}█", value), Style::default().fg(Color::White)),
+            Style::default().fg(NEON_MAGENTA),
+        ),
+        None => {
+            let summary: Vec<String> = fields
+                .iter()
+                .filter(|(_, value, _)| !value.is_empty())
+                .map(|(label, value, _)| format!("{}: {}", label, value))
+                .collect();
+            let summary = if summary.is_empty() {
+                "none".to_string()
+            } else {
+                summary.join(" · ")
+            };
+            (
+                " Filters ".to_string(),
+                Span::styled(summary, Style::default().fg(Color::Gray)),
+                Style::default().fg(Color::DarkGray),
             )
-        });
+        }
+    };
+
+    let widget = Paragraph::new(content).block(
+        Block::default()
+            .title(Span::styled(title, Style::default().fg(NEON_CYAN)))
+            .borders(Borders::ALL)
+            .border_style(border_style),
+    );
+    frame.render_widget(widget, area);
+}
+
+fn draw_station_list(frame: &mut Frame, app: &App, table_state: &mut TableState, area: Rect) {
+    let columns = visible_columns(app);
+    let placeholder = missing_placeholder(app);
+    let header_cells = columns.iter().map(|column| {
+        Cell::from(column.header()).style(
+            Style::default()
+                .fg(NEON_CYAN)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        )
+    });
     let header = Row::new(header_cells).height(1).bottom_margin(0);
 
     let station_list = app.current_station_list();
+    let quick_filter = app.quick_filter();
+    let indices: Vec<usize> = if quick_filter.is_some() {
+        app.quick_filtered_indices()
+    } else {
+        (0..station_list.len()).collect()
+    };
+    let window = if app.view_mode == StationViewMode::Favorites && quick_filter.is_none() {
+        let viewport_rows = area.height.saturating_sub(3) as usize;
+        visible_window(
+            station_list.len(),
+            app.selected,
+            app.scroll_offset,
+            viewport_rows,
+        )
+    } else {
+        0..indices.len()
+    };
 
-    let rows: Vec<Row> = if app.view_mode == StationViewMode::Favorites && app.favorites_loading {
+    let rows: Vec<Row> = if app.view_mode == StationViewMode::Favorites
+        && app.favorites_loading
+        && station_list.is_empty()
+    {
         vec![Row::new(vec![Cell::from(Span::styled(
             "Loading favorites...",
             Style::default().fg(Color::Yellow),
         ))])]
+    } else if app.view_mode == StationViewMode::MostPlayed
+        && app.most_played_loading
+        && station_list.is_empty()
+    {
+        vec![Row::new(vec![Cell::from(Span::styled(
+            "Loading most played stations...",
+            Style::default().fg(Color::Yellow),
+        ))])]
     } else if app.view_mode == StationViewMode::AllStations && app.loading {
         vec![Row::new(vec![Cell::from(Span::styled(
             "Loading stations...",
@@ -196,6 +797,12 @@ fn draw_station_list(frame: &mut Frame, app: &App, table_state: &mut TableState,
     } else if station_list.is_empty() {
         let message = if app.view_mode == StationViewMode::Favorites {
             "No favorites yet. Press Space to add one."
+        } else if app.view_mode == StationViewMode::MostPlayed {
+            "No plays recorded yet. Play a station to see it here."
+        } else if app.view_mode == StationViewMode::History {
+            "Nothing played yet this session."
+        } else if app.latest_station_request_id == 0 {
+            "Press / to search or f for favorites."
         } else {
             "No stations found. Try different filters."
         };
@@ -203,11 +810,16 @@ fn draw_station_list(frame: &mut Frame, app: &App, table_state: &mut TableState,
             message,
             Style::default().fg(Color::DarkGray),
         ))])]
+    } else if indices.is_empty() {
+        vec![Row::new(vec![Cell::from(Span::styled(
+            format!("No matches for \"{}\".", quick_filter.unwrap_or_default()),
+            Style::default().fg(Color::DarkGray),
+        ))])]
     } else {
-        station_list
+        indices[window.clone()]
             .iter()
-            .enumerate()
-            .map(|(i, s)| {
+            .map(|&i| {
+                let s = &station_list[i];
                 let is_playing = app
                     .current_station
                     .as_ref()
@@ -217,16 +829,6 @@ fn draw_station_list(frame: &mut Frame, app: &App, table_state: &mut TableState,
 
                 let playing_prefix = if is_playing { "▶ " } else { "  " };
                 let favorite_prefix = if is_favorite { "★ " } else { "" };
-                let name = format!(
-                    "{}{}{}",
-                    playing_prefix,
-                    favorite_prefix,
-                    truncate(&s.name, 32)
-                );
-                let country = display_country(s);
-                let language = display_language(s);
-                let tags = display_tags(s, 30);
-                let bitrate = display_bitrate(s);
 
                 let style = if i == app.selected {
                     Style::default()
@@ -239,13 +841,27 @@ fn draw_station_list(frame: &mut Frame, app: &App, table_state: &mut TableState,
                     Style::default().fg(Color::White)
                 };
 
-                Row::new(vec![
-                    Cell::from(name).style(style),
-                    Cell::from(country).style(style),
-                    Cell::from(language).style(style),
-                    Cell::from(tags).style(style),
-                    Cell::from(bitrate).style(style),
-                ])
+                Row::new(columns.iter().map(|column| {
+                    if *column != Column::Name {
+                        return Cell::from(column.cell_text(s, placeholder)).style(style);
+                    }
+                    let name = column.cell_text(s, placeholder);
+                    let prefix = format!("{}{}", playing_prefix, favorite_prefix);
+                    match quick_filter.and_then(|query| quick_filter_match(&name, query)) {
+                        Some(range) if !range.is_empty() => Cell::from(Line::from(vec![
+                            Span::raw(format!("{}{}", prefix, &name[..range.start])),
+                            Span::styled(
+                                name[range.clone()].to_string(),
+                                Style::default()
+                                    .fg(Color::Yellow)
+                                    .add_modifier(Modifier::UNDERLINED),
+                            ),
+                            Span::raw(name[range.end..].to_string()),
+                        ]))
+                        .style(style),
+                        _ => Cell::from(format!("{}{}", prefix, name)).style(style),
+                    }
+                }))
                 .height(1)
             })
             .collect()
@@ -253,37 +869,56 @@ fn draw_station_list(frame: &mut Frame, app: &App, table_state: &mut TableState,
 
     let title = app.stations_title();
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Min(20),
-            Constraint::Length(8),
-            Constraint::Length(14),
-            Constraint::Min(20),
-            Constraint::Length(10),
-        ],
-    )
-    .header(header)
-    .block(
-        Block::default()
-            .title(Span::styled(
-                title,
-                Style::default().fg(NEON_CYAN).add_modifier(Modifier::BOLD),
-            ))
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(NEON_MAGENTA)),
-    )
-    .row_highlight_style(
-        Style::default()
-            .bg(SELECTED_BG)
-            .fg(NEON_MAGENTA)
-            .add_modifier(Modifier::BOLD),
-    );
+    let table = Table::new(rows, columns.iter().map(|column| column.constraint()))
+        .header(header)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    title,
+                    Style::default().fg(NEON_CYAN).add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(NEON_MAGENTA)),
+        )
+        .row_highlight_style(
+            Style::default()
+                .bg(SELECTED_BG)
+                .fg(NEON_MAGENTA)
+                .add_modifier(Modifier::BOLD),
+        );
 
-    *table_state = TableState::default()
-        .with_offset(app.scroll_offset)
-        .with_selected(Some(app.selected));
+    let selected_row = indices.iter().position(|index| *index == app.selected);
+    *table_state = if quick_filter.is_some() {
+        TableState::default().with_selected(selected_row)
+    } else if app.view_mode == StationViewMode::Favorites {
+        TableState::default().with_selected(Some(app.selected.saturating_sub(window.start)))
+    } else {
+        TableState::default()
+            .with_offset(app.scroll_offset)
+            .with_selected(Some(app.selected))
+    };
     frame.render_stateful_widget(table, area, table_state);
+    if window.start > 0 {
+        *table_state = TableState::default()
+            .with_offset(window.start + table_state.offset())
+            .with_selected(Some(app.selected));
+    }
+
+    let viewport_rows = area.height.saturating_sub(3) as usize;
+    if indices.len() > viewport_rows {
+        let mut scrollbar_state = ScrollbarState::new(indices.len())
+            .viewport_content_length(viewport_rows)
+            .position(selected_row.unwrap_or(app.selected));
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .style(Style::default().fg(NEON_MAGENTA)),
+            area.inner(Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut scrollbar_state,
+        );
+    }
 }
 
 fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
@@ -293,17 +928,53 @@ fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
             key("Enter", "Apply & Search"),
             key("Esc", "Cancel"),
         ]
+    } else if app.mode == AppMode::Command {
+        vec![key("Enter", "Run Command"), key("Esc", "Cancel")]
+    } else if app.mode == AppMode::ConfirmQuit {
+        vec![key("y", "Quit"), key("n/Esc", "Cancel")]
+    } else if app.mode == AppMode::Help {
+        vec![key(
+            format!("{}/Esc", app.keymap.label(Action::Help)),
+            "Close Help",
+        )]
+    } else if app.quick_filter().is_some() {
+        vec![
+            key("↑↓", "Navigate Matches"),
+            key("Enter", "Play"),
+            key("Esc", "Clear"),
+        ]
     } else {
+        let keymap = &app.keymap;
         vec![
             key("↑↓", "Navigate"),
             key("Enter", "Play"),
-            key("Space", "Favorite"),
-            key("f", "Favorites"),
-            key("/", "Filter"),
-            key("n/p", "Next/Prev Page"),
-            key("+/-", "Volume"),
-            key("s", "Stop"),
-            key("q", "Quit"),
+            key(keymap.label(Action::Details), "Details"),
+            key(keymap.label(Action::Favorite), "Favorite"),
+            key(keymap.label(Action::Favorites), "Favorites"),
+            key(keymap.label(Action::Filter), "Filter"),
+            key(
+                format!(
+                    "{}/{}",
+                    keymap.label(Action::NextPage),
+                    keymap.label(Action::PrevPage)
+                ),
+                "Next/Prev Page",
+            ),
+            key(
+                format!(
+                    "{}/{}",
+                    keymap.label(Action::VolumeUp),
+                    keymap.label(Action::VolumeDown)
+                ),
+                "Volume",
+            ),
+            key(keymap.label(Action::Stop), "Stop"),
+            key(keymap.label(Action::Share), "Share"),
+            key(keymap.label(Action::Mini), "Mini"),
+            key(keymap.label(Action::External), "External"),
+            key(keymap.label(Action::Command), "Command"),
+            key(keymap.label(Action::Help), "Help"),
+            key(keymap.label(Action::Quit), "Quit"),
         ]
     };
 
@@ -350,15 +1021,82 @@ fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
         format!("Vol: {}%", app.volume_display()),
         Style::default().fg(NEON_CYAN),
     ));
+    if !app.favorites.is_empty() {
+        second_spans.push(Span::styled("  │  ", Style::default().fg(Color::DarkGray)));
+        second_spans.push(Span::styled(
+            format!("★ {}", app.favorites.len()),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    let mut lines = vec![Line::from(first_spans), Line::from(second_spans)];
+    if app.mode == AppMode::Command {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(":{}", app.command_input),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled("█", Style::default().fg(NEON_MAGENTA)),
+        ]));
+    } else if let Some(query) = app.quick_filter() {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{}{}", app.keymap.label(Action::QuickFilter), query),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled("█", Style::default().fg(NEON_MAGENTA)),
+            Span::styled(
+                format!(
+                    "  {} of {} loaded stations",
+                    app.quick_filtered_indices().len(),
+                    app.current_station_list().len()
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    } else if app.mode == AppMode::ConfirmQuit {
+        lines.push(Line::from(Span::styled(
+            "Quit cradio? (y/n)",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+    } else if let Some(status) = &app.status {
+        lines.push(Line::from(Span::styled(
+            status.as_str(),
+            Style::default().fg(Color::Yellow),
+        )));
+    } else if app.view_mode == StationViewMode::Favorites && app.favorites.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "Press {} on a station to add favorites",
+                app.keymap.label(Action::Favorite)
+            ),
+            Style::default().fg(NEON_MAGENTA),
+        )));
+    }
 
-    let footer = Paragraph::new(vec![Line::from(first_spans), Line::from(second_spans)])
+    let footer = Paragraph::new(lines)
         .alignment(Alignment::Left)
         .block(Block::default().borders(Borders::NONE));
     frame.render_widget(footer, area);
 }
 
-fn key<'a>(k: &'a str, desc: &'a str) -> (&'a str, &'a str) {
-    (k, desc)
+fn key(k: impl Into<String>, desc: &str) -> (String, &str) {
+    (k.into(), desc)
+}
+
+fn visible_window(len: usize, selected: usize, scroll_offset: usize, rows: usize) -> Range<usize> {
+    if rows == 0 || len == 0 {
+        return 0..0;
+    }
+    let mut start = scroll_offset.min(len.saturating_sub(1));
+    if selected < start {
+        start = selected;
+    } else if selected >= start + rows {
+        start = selected + 1 - rows;
+    }
+    start..(start + rows).min(len)
 }
 
 fn truncate(s: &str, max: usize) -> String {
@@ -371,39 +1109,65 @@ fn truncate(s: &str, max: usize) -> String {
     }
 }
 
-fn display_country(station: &crate::api::Station) -> String {
-    if station.country_code.is_empty() {
-        "N/A".to_string()
+fn missing_placeholder(app: &App) -> &'static str {
+    if app.settings.hide_na {
+        ""
     } else {
-        station.country_code.clone()
+        NOT_AVAILABLE
     }
 }
 
-fn display_language(station: &crate::api::Station) -> String {
-    if station.language.is_empty() {
-        "N/A".to_string()
+fn display_or_na(text: &str, placeholder: &str) -> String {
+    if text.is_empty() {
+        placeholder.to_string()
     } else {
-        truncate(&station.language, 12)
+        text.to_string()
     }
 }
 
-fn display_tags(station: &crate::api::Station, max: usize) -> String {
-    truncate(&station.tags, max)
-}
+fn display_country(station: &Station, placeholder: &str) -> String {
+    if station.country_code.is_empty() {
+        placeholder.to_string()
+    } else {
+        station.country_code.clone()
+    }
+}
 
-fn display_bitrate(station: &crate::api::Station) -> String {
+fn display_language(station: &Station, placeholder: &str) -> String {
+    if station.language.is_empty() {
+        placeholder.to_string()
+    } else {
+        truncate(&station.language, 12)
+    }
+}
+
+fn display_tags(station: &Station, max: usize) -> String {
+    truncate(&station.tags, max)
+}
+
+fn display_bitrate(station: &Station, placeholder: &str) -> String {
     if station.bitrate > 0 {
         format!("{} kbps", station.bitrate)
     } else {
-        String::from("N/A")
+        placeholder.to_string()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::draw;
-    use crate::{api::Station, app::App};
-    use ratatui::{Terminal, backend::TestBackend, buffer::Buffer, widgets::TableState};
+    use super::{Column, draw, monochrome_from_env, visible_columns, visible_window};
+    use crate::{
+        api::Station,
+        app::{App, AppMode, InputField, StationViewMode},
+        player::PlaybackState,
+    };
+    use ratatui::{
+        Terminal,
+        backend::TestBackend,
+        buffer::Buffer,
+        style::{Color, Modifier},
+        widgets::TableState,
+    };
 
     fn station(id: &str) -> Station {
         Station {
@@ -415,6 +1179,10 @@ mod tests {
             country_code: String::new(),
             language: String::new(),
             bitrate: 0,
+            favicon: String::new(),
+            votes: 0,
+            codec: String::new(),
+            homepage: String::new(),
         }
     }
 
@@ -430,6 +1198,137 @@ mod tests {
         text.contains(needle)
     }
 
+    fn render(app: &App, width: u16, height: u16) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("terminal");
+        let mut table_state = TableState::default();
+        terminal
+            .draw(|frame| draw(frame, app, &mut table_state))
+            .expect("draw");
+        terminal.backend().buffer().clone()
+    }
+
+    #[test]
+    fn draw_shows_loading_stations_message() {
+        let mut app = App::new();
+        app.loading = true;
+
+        let buffer = render(&app, 100, 24);
+        assert!(buffer_contains(&buffer, "Loading stations..."));
+    }
+
+    #[test]
+    fn draw_shows_loading_favorites_message_while_list_is_empty() {
+        let mut app = App::new();
+        app.set_view_mode(StationViewMode::Favorites);
+        app.favorites_loading = true;
+
+        let buffer = render(&app, 100, 24);
+        assert!(buffer_contains(&buffer, "Loading favorites..."));
+        assert!(buffer_contains(&buffer, "Favorites - refreshing..."));
+    }
+
+    #[test]
+    fn draw_shows_station_load_error() {
+        let mut app = App::new();
+        app.set_error("API error: 503".to_string());
+
+        let buffer = render(&app, 100, 24);
+        assert!(buffer_contains(&buffer, "Error: API error: 503"));
+        assert!(!buffer_contains(&buffer, "Loading stations..."));
+    }
+
+    #[test]
+    fn draw_refreshing_without_favorites_shows_empty_state_without_loading() {
+        let mut app = App::new();
+        app.favorites_loading = true;
+
+        assert_eq!(app.refresh_favorites(), None);
+
+        let buffer = render(&app, 100, 24);
+        assert!(buffer_contains(&buffer, "No favorites yet."));
+        assert!(!buffer_contains(&buffer, "Loading favorites..."));
+        assert!(!buffer_contains(&buffer, "refreshing..."));
+        assert!(!app.favorites_loading);
+        assert_eq!(app.view_mode, StationViewMode::Favorites);
+    }
+
+    #[test]
+    fn draw_shows_empty_state_messages_per_view() {
+        let mut app = App::new();
+        app.note_station_request();
+        let buffer = render(&app, 100, 24);
+        assert!(buffer_contains(&buffer, "No stations found."));
+
+        app.set_view_mode(StationViewMode::Favorites);
+        let buffer = render(&app, 100, 24);
+        assert!(buffer_contains(&buffer, "No favorites yet."));
+    }
+
+    #[test]
+    fn draw_shows_search_hint_before_first_search() {
+        let mut app = App::new();
+        let buffer = render(&app, 100, 24);
+        assert!(buffer_contains(&buffer, "Press / to search"));
+
+        app.note_station_request();
+        let buffer = render(&app, 100, 24);
+        assert!(!buffer_contains(&buffer, "Press / to search"));
+    }
+
+    #[test]
+    fn draw_lists_loaded_stations_with_page_title() {
+        let mut app = App::new();
+        app.set_stations(vec![station("a"), station("b")]);
+
+        let buffer = render(&app, 100, 24);
+        assert!(buffer_contains(&buffer, "Station a"));
+        assert!(buffer_contains(&buffer, "Station b"));
+        assert!(buffer_contains(
+            &buffer,
+            "Stations ▼ - Page 1 - end reached"
+        ));
+        assert!(buffer_contains(&buffer, "No station playing"));
+    }
+
+    #[test]
+    fn draw_in_filter_mode_shows_cursor_and_filter_keys() {
+        let mut app = App::new();
+        app.mode = AppMode::Filtering(InputField::Tags);
+        app.draft_tags = "jazz".to_string();
+
+        let buffer = render(&app, 120, 24);
+        assert!(buffer_contains(&buffer, "jazz█"));
+        assert!(buffer_contains(&buffer, "Apply & Search"));
+        assert!(!buffer_contains(&buffer, "Next/Prev Page"));
+    }
+
+    #[test]
+    fn draw_filters_shows_only_active_field_on_narrow_terminals() {
+        let mut app = App::new();
+        app.mode = AppMode::Filtering(InputField::Language);
+        app.draft_language = "german".to_string();
+
+        let buffer = render(&app, 60, 24);
+        assert!(buffer_contains(&buffer, "Lang (4/7)"));
+        assert!(buffer_contains(&buffer, "german█"));
+        assert!(!buffer_contains(&buffer, "┌ Name "));
+    }
+
+    #[test]
+    fn draw_filters_summarises_filters_on_narrow_terminals() {
+        let mut app = App::new();
+        app.draft_name = "jazz".to_string();
+        app.draft_country = "DE".to_string();
+
+        let buffer = render(&app, 60, 24);
+        assert!(buffer_contains(&buffer, "Name: jazz · Country: DE"));
+
+        app.settings.compact_filters_below = 0;
+        let buffer = render(&app, 60, 24);
+        assert!(!buffer_contains(&buffer, " Filters "));
+        assert!(buffer_contains(&buffer, "┌ Name "));
+    }
+
     #[test]
     fn draw_renders_inline_playback_error() {
         let backend = TestBackend::new(80, 20);
@@ -462,6 +1361,10 @@ mod tests {
             country_code: "US".to_string(),
             language: "english".to_string(),
             bitrate: 320,
+            favicon: String::new(),
+            votes: 0,
+            codec: String::new(),
+            homepage: String::new(),
         });
 
         terminal
@@ -476,6 +1379,64 @@ mod tests {
         assert!(buffer_contains(&buffer, "320 kbps"));
     }
 
+    #[test]
+    fn draw_now_playing_marks_active_recordings() {
+        let mut app = App::new();
+        app.current_station = Some(station("a"));
+        assert!(!buffer_contains(&render(&app, 100, 24), "● REC"));
+
+        app.recording = Some(std::path::PathBuf::from("/rec/a.mp3"));
+        assert!(buffer_contains(&render(&app, 100, 24), "● REC"));
+    }
+
+    #[test]
+    fn draw_now_playing_appends_the_stream_title() {
+        let mut app = App::new();
+        app.current_station = Some(station("a"));
+        assert!(!buffer_contains(&render(&app, 120, 24), "So What"));
+
+        app.current_track = Some("Miles Davis - So What".to_string());
+        assert!(buffer_contains(
+            &render(&app, 120, 24),
+            " — Miles Davis - So What"
+        ));
+    }
+
+    #[test]
+    fn draw_now_playing_shows_the_pause_symbol_while_paused() {
+        let mut app = App::new();
+        app.current_station = Some(station("a"));
+        assert!(!buffer_contains(&render(&app, 100, 24), "⏸"));
+
+        app.paused = true;
+        assert!(buffer_contains(&render(&app, 100, 24), "⏸"));
+    }
+
+    #[test]
+    fn draw_now_playing_shows_spinner_while_checking_stream() {
+        let mut app = App::new();
+        app.checking_station = Some(station("a"));
+        app.playback_error = Some("old failure".to_string());
+
+        let buffer = render(&app, 100, 24);
+        assert!(buffer_contains(&buffer, "Checking… Station a"));
+        assert!(!buffer_contains(&buffer, "old failure"));
+    }
+
+    #[test]
+    fn draw_now_playing_shows_connecting_until_playback_is_confirmed() {
+        let mut app = App::new();
+        app.current_station = Some(station("a"));
+        app.playback_state = PlaybackState::Connecting;
+
+        let buffer = render(&app, 100, 24);
+        assert!(buffer_contains(&buffer, "Connecting… Station a"));
+        assert!(!buffer_contains(&buffer, "▶ Station a"));
+
+        app.playback_state = PlaybackState::Playing;
+        assert!(buffer_contains(&render(&app, 100, 24), "▶ Station a"));
+    }
+
     #[test]
     fn draw_now_playing_uses_na_for_missing_metadata() {
         let backend = TestBackend::new(100, 20);
@@ -491,6 +1452,10 @@ mod tests {
             country_code: String::new(),
             language: String::new(),
             bitrate: 0,
+            favicon: String::new(),
+            votes: 0,
+            codec: String::new(),
+            homepage: String::new(),
         });
 
         terminal
@@ -519,4 +1484,396 @@ mod tests {
         assert_eq!(table_state.selected(), Some(8));
         assert_eq!(table_state.offset(), 4);
     }
+
+    #[test]
+    fn draw_reports_the_first_drawn_favorite_as_table_offset() {
+        let backend = TestBackend::new(80, 20);
+        let mut terminal = Terminal::new(backend).expect("terminal");
+        let mut app = App::new();
+        let mut table_state = TableState::default();
+        app.view_mode = StationViewMode::Favorites;
+        app.set_favorite_stations((0..30).map(|i| station(&format!("{:02}", i))).collect());
+        app.selected = 25;
+        app.scroll_offset = 0;
+
+        terminal
+            .draw(|frame| draw(frame, &app, &mut table_state))
+            .expect("draw");
+
+        let first = table_state.offset();
+        assert!(first > 0);
+        let buffer = terminal.backend().buffer();
+        assert!(buffer_contains(buffer, &format!("Station {:02}", first)));
+        assert!(!buffer_contains(
+            buffer,
+            &format!("Station {:02}", first - 1)
+        ));
+    }
+
+    #[test]
+    fn station_list_shows_a_scrollbar_only_when_rows_overflow() {
+        let right_edge = |buffer: &Buffer| -> String {
+            let x = buffer.area.width - 1;
+            (0..buffer.area.height)
+                .map(|y| buffer[(x, y)].symbol().to_string())
+                .collect()
+        };
+        let mut app = App::new();
+        app.stations = (0..3).map(|i| station(&i.to_string())).collect();
+        assert!(!right_edge(&render(&app, 80, 20)).contains('▼'));
+
+        app.stations = (0..30).map(|i| station(&i.to_string())).collect();
+        let top = right_edge(&render(&app, 80, 20));
+        assert!(top.contains('▲') && top.contains('▼'));
+
+        app.selected = 29;
+        app.scroll_offset = 25;
+        let bottom = right_edge(&render(&app, 80, 20));
+        assert_ne!(top, bottom);
+        assert!(bottom.find('█') > top.find('█'));
+    }
+
+    #[test]
+    fn draw_header_shows_network_activity_while_requests_are_in_flight() {
+        let mut app = App::new();
+        let buffer = render(&app, 100, 20);
+        assert!(!buffer_contains(&buffer, "⠋"));
+
+        app.begin_request();
+        let buffer = render(&app, 100, 20);
+        assert!(buffer_contains(&buffer, "⠋"));
+    }
+
+    #[test]
+    fn draw_mini_layout_shows_only_now_playing_and_volume() {
+        let mut app = App::new();
+        app.mini = true;
+        app.set_stations(vec![station("a")]);
+        app.current_station = Some(station("b"));
+
+        let buffer = render(&app, 60, 6);
+        assert!(buffer_contains(&buffer, "Station b"));
+        assert!(buffer_contains(&buffer, "Vol: 50%"));
+        assert!(buffer_contains(&buffer, "m Full view"));
+        assert!(!buffer_contains(&buffer, "Station a"));
+        assert!(!buffer_contains(&buffer, "Name"));
+    }
+
+    #[test]
+    fn draw_footer_shows_status_message() {
+        let backend = TestBackend::new(120, 20);
+        let mut terminal = Terminal::new(backend).expect("terminal");
+        let mut app = App::new();
+        let mut table_state = TableState::default();
+        app.set_status("Session imported".to_string());
+
+        terminal
+            .draw(|frame| draw(frame, &app, &mut table_state))
+            .expect("draw");
+
+        let buffer = terminal.backend().buffer().clone();
+        assert!(buffer_contains(&buffer, "Session imported"));
+    }
+
+    #[test]
+    fn draw_footer_shows_remapped_keys() {
+        let mut app = App::new();
+        app.settings
+            .keys
+            .insert("stop".to_string(), "k".to_string());
+        app.apply_settings(app.settings.clone())
+            .expect("valid keys");
+
+        let buffer = render(&app, 140, 24);
+        assert!(buffer_contains(&buffer, "k Stop"));
+        assert!(!buffer_contains(&buffer, "s Stop"));
+    }
+
+    #[test]
+    fn draw_footer_shows_favorites_count_or_call_to_action() {
+        let mut app = App::new();
+        app.set_view_mode(StationViewMode::Favorites);
+        let buffer = render(&app, 120, 20);
+        assert!(buffer_contains(
+            &buffer,
+            "Press Space on a station to add favorites"
+        ));
+        assert!(!buffer_contains(&buffer, "★ 0"));
+
+        app.stations = vec![station("a"), station("b")];
+        app.set_view_mode(StationViewMode::AllStations);
+        app.toggle_favorite_at(0);
+        app.toggle_favorite_at(1);
+        let buffer = render(&app, 120, 20);
+        assert!(buffer_contains(&buffer, "★ 2"));
+        assert!(!buffer_contains(&buffer, "to add favorites"));
+    }
+
+    #[test]
+    fn draw_footer_asks_before_quitting() {
+        let mut app = App::new();
+        app.set_status("Settings reloaded".to_string());
+        app.mode = AppMode::ConfirmQuit;
+
+        let buffer = render(&app, 120, 20);
+        assert!(buffer_contains(&buffer, "Quit cradio? (y/n)"));
+        assert!(buffer_contains(&buffer, "n/Esc Cancel"));
+        assert!(!buffer_contains(&buffer, "Settings reloaded"));
+    }
+
+    #[test]
+    fn draw_footer_shows_command_line_in_command_mode() {
+        let backend = TestBackend::new(120, 20);
+        let mut terminal = Terminal::new(backend).expect("terminal");
+        let mut app = App::new();
+        let mut table_state = TableState::default();
+        app.set_status("Session imported".to_string());
+        app.mode = AppMode::Command;
+        app.command_input = "goto 3".to_string();
+
+        terminal
+            .draw(|frame| draw(frame, &app, &mut table_state))
+            .expect("draw");
+
+        let buffer = terminal.backend().buffer().clone();
+        assert!(buffer_contains(&buffer, ":goto 3"));
+        assert!(buffer_contains(&buffer, "Run Command"));
+        assert!(!buffer_contains(&buffer, "Session imported"));
+    }
+
+    #[test]
+    fn visible_window_keeps_selection_inside_viewport() {
+        assert_eq!(visible_window(500, 0, 0, 10), 0..10);
+        assert_eq!(visible_window(500, 42, 30, 10), 33..43);
+        assert_eq!(visible_window(500, 5, 30, 10), 5..15);
+        assert_eq!(visible_window(500, 499, 495, 10), 495..500);
+        assert_eq!(visible_window(3, 1, 0, 10), 0..3);
+        assert_eq!(visible_window(0, 0, 0, 10), 0..0);
+    }
+
+    #[test]
+    fn draw_favorites_renders_only_visible_window() {
+        let backend = TestBackend::new(80, 20);
+        let mut terminal = Terminal::new(backend).expect("terminal");
+        let mut app = App::new();
+        let mut table_state = TableState::default();
+        app.favorite_stations = (0..500).map(|i| station(&i.to_string())).collect();
+        app.set_view_mode(StationViewMode::Favorites);
+        app.selected = 300;
+        app.scroll_offset = 298;
+
+        terminal
+            .draw(|frame| draw(frame, &app, &mut table_state))
+            .expect("draw");
+
+        let buffer = terminal.backend().buffer().clone();
+        assert!(buffer_contains(&buffer, "Station 298"));
+        assert!(buffer_contains(&buffer, "Station 300"));
+        assert!(!buffer_contains(&buffer, "Station 297"));
+        assert_eq!(table_state.offset(), 298);
+        assert_eq!(table_state.selected(), Some(300));
+    }
+
+    #[test]
+    fn draw_shows_whats_new_popup_over_the_station_list() {
+        let backend = TestBackend::new(100, 30);
+        let mut terminal = Terminal::new(backend).expect("terminal");
+        let mut app = App::new();
+        let mut table_state = TableState::default();
+        app.whats_new = Some("### Added\n\n- Pause support.".to_string());
+
+        terminal
+            .draw(|frame| draw(frame, &app, &mut table_state))
+            .expect("draw");
+
+        let buffer = terminal.backend().buffer().clone();
+        assert!(buffer_contains(&buffer, "What's new in v"));
+        assert!(buffer_contains(&buffer, "- Pause support."));
+        assert!(buffer_contains(&buffer, "Press any key to continue"));
+    }
+
+    #[test]
+    fn draw_details_popup_shows_full_station_metadata() {
+        let backend = TestBackend::new(120, 30);
+        let mut terminal = Terminal::new(backend).expect("terminal");
+        let mut app = App::new();
+        let mut table_state = TableState::default();
+        let mut detailed = station("id-1");
+        detailed.tags = "jazz,blues,swing,bebop,soul".to_string();
+        detailed.favicon = "https://example.com/icon.png".to_string();
+        app.stations = vec![detailed];
+        app.show_details = true;
+        app.record_play("id-1");
+        app.record_play("id-1");
+
+        terminal
+            .draw(|frame| draw(frame, &app, &mut table_state))
+            .expect("draw");
+
+        let buffer = terminal.backend().buffer().clone();
+        assert!(buffer_contains(&buffer, "Station Details"));
+        assert!(buffer_contains(&buffer, "jazz,blues,swing,bebop,soul"));
+        assert!(buffer_contains(&buffer, "https://example.com/icon.png"));
+        assert!(buffer_contains(&buffer, "2 this session, 2 total"));
+    }
+
+    #[test]
+    fn draw_help_lists_keys_by_category_in_every_view() {
+        let mut app = App::new();
+        app.mode = AppMode::Help;
+        app.settings
+            .keys
+            .insert("stop".to_string(), "k".to_string());
+        app.apply_settings(app.settings.clone())
+            .expect("valid keys");
+
+        for view in [StationViewMode::AllStations, StationViewMode::Favorites] {
+            app.view_mode = view;
+            let buffer = render(&app, 120, 60);
+            assert!(buffer_contains(&buffer, "Help - ? or Esc to close"));
+            for title in ["Navigation", "Playback", "Favorites", "Filters"] {
+                assert!(buffer_contains(&buffer, title));
+            }
+            assert!(buffer_contains(&buffer, "k  Stop"));
+            assert!(buffer_contains(&buffer, "Close Help"));
+            assert!(buffer_contains(&buffer, "?/Esc"));
+        }
+    }
+
+    #[test]
+    fn monochrome_is_enabled_by_no_color_or_dumb_terminals() {
+        assert!(!monochrome_from_env(None, Some("xterm-256color")));
+        assert!(!monochrome_from_env(Some(""), None));
+        assert!(monochrome_from_env(Some("1"), Some("xterm-256color")));
+        assert!(monochrome_from_env(None, Some("dumb")));
+    }
+
+    #[test]
+    fn monochrome_drops_colors_but_keeps_the_selection_visible() {
+        let mut app = App::new();
+        app.monochrome = true;
+        app.set_stations(vec![station("a"), station("b")]);
+        app.current_station = Some(station("b"));
+
+        let buffer = render(&app, 100, 20);
+        assert!(
+            buffer
+                .content
+                .iter()
+                .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset)
+        );
+        assert!(
+            buffer
+                .content
+                .iter()
+                .any(|cell| cell.symbol() == "S" && cell.modifier.contains(Modifier::REVERSED))
+        );
+        assert!(buffer_contains(&buffer, "▶ Station b"));
+    }
+
+    #[test]
+    fn hide_na_leaves_missing_fields_blank() {
+        let mut app = App::new();
+        app.settings.hide_na = true;
+        app.set_stations(vec![station("plain")]);
+        app.current_station = Some(station("plain"));
+
+        let buffer = render(&app, 120, 20);
+        assert!(buffer_contains(&buffer, "Station plain"));
+        assert!(!buffer_contains(&buffer, "N/A"));
+
+        app.settings.hide_na = false;
+        let buffer = render(&app, 120, 20);
+        assert!(buffer_contains(&buffer, "N/A"));
+        assert!(buffer_contains(&buffer, "| N/A |  | N/A"));
+    }
+
+    #[test]
+    fn default_columns_fit_an_80_column_terminal() {
+        let mut app = App::new();
+        let mut wide = station("id-1");
+        wide.language = "portuguese,english".to_string();
+        wide.bitrate = 320;
+        wide.codec = "AAC+".to_string();
+        app.stations = vec![wide];
+
+        let buffer = render(&app, 80, 24);
+        for header in ["Name", "Country", "Language", "Tags", "Bitrate", "Codec"] {
+            assert!(buffer_contains(&buffer, header), "missing {}", header);
+        }
+        assert!(buffer_contains(&buffer, "320 kbps"));
+        assert!(buffer_contains(&buffer, "AAC+"));
+    }
+
+    #[test]
+    fn quick_filter_shows_only_matching_rows_with_the_match_highlighted() {
+        let mut app = App::new();
+        app.stations = vec![station("jazz"), station("rock"), station("jazzy")];
+        app.start_quick_filter();
+        for c in "JAZ".chars() {
+            app.push_quick_filter_char(c);
+        }
+
+        let buffer = render(&app, 100, 24);
+        assert!(buffer_contains(&buffer, "Station jazz"));
+        assert!(buffer_contains(&buffer, "Station jazzy"));
+        assert!(!buffer_contains(&buffer, "Station rock"));
+        assert!(buffer_contains(&buffer, "&JAZ█  2 of 3 loaded stations"));
+        let area = buffer.area();
+        let highlighted = (0..area.height)
+            .flat_map(|y| (0..area.width).map(move |x| (x, y)))
+            .filter(|pos| buffer[*pos].modifier.contains(Modifier::UNDERLINED))
+            .filter(|pos| buffer[*pos].fg == Color::Yellow)
+            .count();
+        // The selected row is restyled by the table highlight, so only "jazzy" stays yellow.
+        assert_eq!(highlighted, 3);
+
+        app.push_quick_filter_char('q');
+        let buffer = render(&app, 100, 24);
+        assert!(buffer_contains(&buffer, "No matches for \"JAZq\"."));
+    }
+
+    #[test]
+    fn draw_station_list_uses_configured_columns() {
+        let backend = TestBackend::new(100, 20);
+        let mut terminal = Terminal::new(backend).expect("terminal");
+        let mut app = App::new();
+        let mut table_state = TableState::default();
+        let mut voted = station("id-1");
+        voted.votes = 4242;
+        app.stations = vec![voted];
+        app.settings.columns = vec![
+            "name".to_string(),
+            "votes".to_string(),
+            "bogus".to_string(),
+            "votes".to_string(),
+        ];
+
+        terminal
+            .draw(|frame| draw(frame, &app, &mut table_state))
+            .expect("draw");
+
+        let buffer = terminal.backend().buffer().clone();
+        assert!(buffer_contains(&buffer, "Votes"));
+        assert!(buffer_contains(&buffer, "4242"));
+        assert!(!buffer_contains(&buffer, "Language"));
+    }
+
+    #[test]
+    fn visible_columns_fall_back_to_defaults_when_none_are_valid() {
+        let mut app = App::new();
+        app.settings.columns = vec!["nope".to_string()];
+
+        assert_eq!(
+            visible_columns(&app),
+            vec![
+                Column::Name,
+                Column::Country,
+                Column::Language,
+                Column::Tags,
+                Column::Bitrate,
+                Column::Codec,
+            ]
+        );
+    }
 }